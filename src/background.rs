@@ -0,0 +1,125 @@
+//! Procedural backdrops.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// A procedural backdrop for a render area.
+///
+/// Draw it right after [`RenderArea2D::set`], in place of clearing the background.
+#[derive(Debug, Clone)]
+pub enum Background {
+    /// A vertical gradient that stays fixed to the view
+    Gradient {
+        /// Color at the top of the view
+        top: Color,
+        /// Color at the bottom of the view
+        bottom: Color,
+    },
+    /// Layers of stars scrolling with the camera
+    Starfield(Starfield),
+}
+
+/// Configuration for [`Background::Starfield`]
+#[derive(Debug, Clone)]
+pub struct Starfield {
+    /// Color behind the stars
+    pub space: Color,
+    /// Color of the nearest stars. Farther layers are dimmer.
+    pub star: Color,
+    /// Number of layers. The nearest layer moves with the camera, farther ones move slower.
+    pub layers: u8,
+    /// Chance for a star to appear in each 16x16 cell of a layer, from `0.0` to `1.0`
+    pub density: f32,
+    /// Seed for star placement
+    pub seed: u32,
+}
+
+impl Default for Starfield {
+    fn default() -> Self {
+        Self {
+            space: BLACK,
+            star: WHITE,
+            layers: 3,
+            density: 0.15,
+            seed: 0,
+        }
+    }
+}
+
+const CELL: f32 = 16.0;
+
+impl Background {
+    /// Draw the background over the whole visible part of `ra`.
+    ///
+    /// `ra` must be set for drawing.
+    pub fn draw(&self, ra: &RenderArea2D) {
+        let view = ra.visible_world_rect();
+        match self {
+            Self::Gradient { top, bottom } => {
+                draw_vertical_gradient(view, *top, *bottom);
+            }
+            Self::Starfield(sf) => sf.draw(view, ra.camera.target),
+        }
+    }
+}
+
+impl Starfield {
+    fn draw(&self, view: Rect, cam: Vec2) {
+        draw_rectangle(view.x, view.y, view.w, view.h, self.space);
+        for layer in 0..self.layers {
+            // Nearest layer last, so it's drawn on top
+            let depth = self.layers - layer;
+            let parallax = 1.0 / f32::from(depth);
+            let brightness = parallax.sqrt();
+            let color = Color {
+                a: self.star.a * brightness,
+                ..self.star
+            };
+            // Stars live in layer space, which is shifted by the part of the camera
+            // movement the layer doesn't follow
+            let shift = cam * (1.0 - parallax);
+            let min = ((view.point() - shift) / CELL).floor().as_ivec2();
+            let max = ((view.point() + view.size() - shift) / CELL)
+                .ceil()
+                .as_ivec2();
+            for cy in min.y..max.y {
+                for cx in min.x..max.x {
+                    let h = hash(cx, cy, self.seed ^ u32::from(layer));
+                    if (h & 0xFFFF) as f32 / 65536.0 >= self.density {
+                        continue;
+                    }
+                    let ox = ((h >> 16) & 0xFF) as f32 / 256.0;
+                    let oy = (h >> 24) as f32 / 256.0;
+                    let pos = (vec2(cx as f32 + ox, cy as f32 + oy) * CELL + shift).floor();
+                    draw_rectangle(pos.x, pos.y, 1.0, 1.0, color);
+                }
+            }
+        }
+    }
+}
+
+/// Draw `rect` filled with a vertical gradient
+pub(crate) fn draw_vertical_gradient(rect: Rect, top: Color, bottom: Color) {
+    let (l, t, r, b) = (rect.x, rect.y, rect.x + rect.w, rect.y + rect.h);
+    draw_mesh(&Mesh {
+        vertices: vec![
+            Vertex::new(l, t, 0.0, 0.0, 0.0, top),
+            Vertex::new(r, t, 0.0, 1.0, 0.0, top),
+            Vertex::new(r, b, 0.0, 1.0, 1.0, bottom),
+            Vertex::new(l, b, 0.0, 0.0, 1.0, bottom),
+        ],
+        indices: vec![0, 1, 2, 0, 2, 3],
+        texture: None,
+    });
+}
+
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ seed.wrapping_mul(0xcb1a_b31f);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297a_2d39);
+    h ^ (h >> 15)
+}
@@ -0,0 +1,99 @@
+//! Blurring the frozen frame behind pause menus.
+
+use crate::{memory::texture_bytes, shader, EffectPass, PassCtx, RenderArea2D};
+use macroquad::prelude::*;
+
+/// 9-tap gaussian along `Step`, one texel apart at strength 1
+const BLUR: &str = r#"#version 100
+precision mediump float;
+varying vec2 uv;
+uniform sampler2D Texture;
+uniform vec2 Step;
+
+void main() {
+    vec4 c = texture2D(Texture, uv) * 0.2270270;
+    c += (texture2D(Texture, uv + Step) + texture2D(Texture, uv - Step)) * 0.1945946;
+    c += (texture2D(Texture, uv + Step * 2.0) + texture2D(Texture, uv - Step * 2.0)) * 0.1216216;
+    c += (texture2D(Texture, uv + Step * 3.0) + texture2D(Texture, uv - Step * 3.0)) * 0.0540541;
+    c += (texture2D(Texture, uv + Step * 4.0) + texture2D(Texture, uv - Step * 4.0)) * 0.0162162;
+    gl_FragColor = c;
+}
+"#;
+
+/// Name of the pass added by [`RenderArea2D::blur_background`]
+const BLUR_PASS: &str = "background-blur";
+
+/// Separable gaussian blur, horizontally into a scratch target, then vertically
+struct BlurPass {
+    material: Material,
+    strength: f32,
+    scratch: Option<RenderTarget>,
+}
+
+impl EffectPass for BlurPass {
+    fn apply(&mut self, src: Texture2D, dst: &RenderTarget, ctx: &PassCtx) {
+        let size = src.size();
+        let scratch = match &self.scratch {
+            Some(rt) if rt.texture.size() == size => rt.clone(),
+            _ => {
+                let rt = render_target(size.x as u32, size.y as u32);
+                rt.texture.set_filter(FilterMode::Linear);
+                self.scratch.insert(rt).clone()
+            }
+        };
+        let texel = self.strength / size;
+        gl_use_material(&self.material);
+        for (from, to, step) in [
+            (&src, &scratch, vec2(texel.x, 0.0)),
+            (&scratch.texture, dst, vec2(0.0, texel.y)),
+        ] {
+            ctx.set_target(to);
+            clear_background(BLANK);
+            self.material.set_uniform("Step", step);
+            draw_texture(from, 0.0, 0.0, WHITE);
+        }
+        gl_use_default_material();
+    }
+    fn gpu_bytes(&self) -> usize {
+        self.scratch
+            .as_ref()
+            .map_or(0, |rt| texture_bytes(&rt.texture))
+    }
+}
+
+impl RenderArea2D {
+    /// Freeze the presented frame and blur it, for showing behind a pause menu.
+    ///
+    /// This [pauses](Self::debug_pause) the area and adds a blur pass at the end of the
+    /// effect chain, so the frozen frame is presented blurred while you draw the menu
+    /// over it. `strength` is roughly the blur radius in virtual pixels divided by 4.
+    /// Calling this again changes the strength. Undo it with [`Self::unblur_background`].
+    pub fn blur_background(&mut self, strength: f32) {
+        self.debug_pause();
+        self.remove_blur_pass();
+        self.add_pass(
+            BLUR_PASS,
+            BlurPass {
+                material: shader::load(
+                    BLUR,
+                    MaterialParams {
+                        uniforms: vec![UniformDesc::new("Step", UniformType::Float2)],
+                        ..Default::default()
+                    },
+                ),
+                strength,
+                scratch: None,
+            },
+        );
+    }
+    /// Remove the blur of [`Self::blur_background`] and resume presenting live frames
+    pub fn unblur_background(&mut self) {
+        self.remove_blur_pass();
+        self.debug_resume();
+    }
+    fn remove_blur_pass(&mut self) {
+        if let Some(chain) = &mut self.effects {
+            chain.get_mut().passes.retain(|e| e.name != BLUR_PASS);
+        }
+    }
+}
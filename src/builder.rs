@@ -0,0 +1,84 @@
+//! Configuring a render area before creating it.
+
+use crate::{LetterboxStyle, RenderArea2D, ScaleMode};
+use macroquad::prelude::*;
+
+/// Builder for [`RenderArea2D`], see [`RenderArea2D::builder`].
+///
+/// Anything not set keeps the same default as [`RenderArea2D::new`].
+#[must_use]
+pub struct RenderArea2DBuilder {
+    width: u16,
+    height: u16,
+    filter: FilterMode,
+    scale_mode: ScaleMode,
+    camera_target: Option<Vec2>,
+    clear_color: Option<Color>,
+    letterbox: LetterboxStyle,
+    flip_y: bool,
+}
+
+impl RenderArea2DBuilder {
+    /// Filter used when scaling the area to the window, see [`RenderArea2D::set_filter`]
+    pub fn filter(mut self, filter: FilterMode) -> Self {
+        self.filter = filter;
+        self
+    }
+    /// How the area is scaled to fit the window, see [`RenderArea2D::set_scale_mode`]
+    pub fn scale_mode(mut self, mode: ScaleMode) -> Self {
+        self.scale_mode = mode;
+        self
+    }
+    /// World position the camera starts out centered on
+    pub fn camera_target(mut self, target: impl Into<Vec2>) -> Self {
+        self.camera_target = Some(target.into());
+        self
+    }
+    /// Color the area is cleared with every frame, see [`RenderArea2D::set_clear_color`]
+    pub fn clear_color(mut self, color: Color) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+    /// What to fill the bars around the area with, see [`RenderArea2D::set_letterbox`]
+    pub fn letterbox(mut self, style: LetterboxStyle) -> Self {
+        self.letterbox = style;
+        self
+    }
+    /// Make world y point up instead of down, see [`RenderArea2D::set_flip_y`]
+    pub fn flip_y(mut self, flip: bool) -> Self {
+        self.flip_y = flip;
+        self
+    }
+    /// Create the render area
+    pub fn build(self) -> RenderArea2D {
+        let mut ra = RenderArea2D::new(self.width, self.height);
+        ra.set_filter(self.filter);
+        ra.set_scale_mode(self.scale_mode);
+        ra.set_letterbox(self.letterbox);
+        ra.set_flip_y(self.flip_y);
+        if let Some(target) = self.camera_target {
+            ra.center_camera_on(target);
+        }
+        if let Some(color) = self.clear_color {
+            ra.clear(color);
+        }
+        ra.set_clear_color(self.clear_color);
+        ra
+    }
+}
+
+impl RenderArea2D {
+    /// Start configuring a render area with the specified virtual resolution
+    pub fn builder(width: u16, height: u16) -> RenderArea2DBuilder {
+        RenderArea2DBuilder {
+            width,
+            height,
+            filter: FilterMode::Nearest,
+            scale_mode: ScaleMode::Integer,
+            camera_target: None,
+            clear_color: None,
+            letterbox: LetterboxStyle::None,
+            flip_y: false,
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Caching rarely-changing world content in textures.
+
+use crate::{memory::texture_bytes, RenderArea2D};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+pub(crate) struct CachedLayer {
+    target: RenderTarget,
+    /// World rect the target covers
+    rect: Rect,
+    /// Camera zoom when rendered, since the target is only 1:1 at that zoom
+    zoom: f32,
+    valid: bool,
+}
+
+pub(crate) struct ChunkedLayer {
+    /// World size of every chunk
+    chunk_size: Vec2,
+    zoom: f32,
+    chunks: HashMap<IVec2, RenderTarget>,
+}
+
+impl CachedLayer {
+    pub(crate) fn gpu_bytes(&self) -> usize {
+        texture_bytes(&self.target.texture)
+    }
+}
+
+impl ChunkedLayer {
+    pub(crate) fn gpu_bytes(&self) -> usize {
+        self.chunks
+            .values()
+            .map(|rt| texture_bytes(&rt.texture))
+            .sum()
+    }
+}
+
+impl RenderArea2D {
+    /// Draw rarely-changing content, like terrain, through a cache called `name`.
+    ///
+    /// Call this every frame while the area is [set](Self::set). `draw` draws in world
+    /// coordinates as usual, but it's only called when the cache needs to be rendered:
+    /// the first time, after [`Self::invalidate_layer`], when the zoom changed, or when
+    /// the camera strayed too far. Otherwise the cached texture is drawn in its place.
+    /// The cache covers the view plus half a view of margin on every side.
+    pub fn cache_layer(&mut self, name: &str, draw: impl FnOnce()) {
+        let view = self.visible_world_rect();
+        let zoom = self.zoom();
+        let needs_render = self
+            .caches
+            .get(name)
+            .is_none_or(|c| !c.valid || c.zoom != zoom || !contains_rect(c.rect, view));
+        if needs_render {
+            let rect = Rect::new(
+                (view.x - view.w / 2.0).floor(),
+                (view.y - view.h / 2.0).floor(),
+                (view.w * 2.0).ceil(),
+                (view.h * 2.0).ceil(),
+            );
+            let px = (rect.size() * zoom).ceil().max(Vec2::ONE);
+            let (w, h) = (px.x as u16, px.y as u16);
+            // The target covers whole pixels, so a tiny bit more than `rect`
+            let rect = Rect::new(rect.x, rect.y, px.x / zoom, px.y / zoom);
+            let cache = match self.caches.remove(name) {
+                Some(c) if c.target.texture.size() == px => c,
+                _ => {
+                    let target = render_target(w.into(), h.into());
+                    target.texture.set_filter(FilterMode::Nearest);
+                    CachedLayer {
+                        target,
+                        rect,
+                        zoom,
+                        valid: false,
+                    }
+                }
+            };
+            push_camera_state();
+            set_camera(&self.cache_camera(&cache.target, rect, zoom));
+            clear_background(BLANK);
+            draw();
+            pop_camera_state();
+            self.caches.insert(
+                name.to_string(),
+                CachedLayer {
+                    rect,
+                    zoom,
+                    valid: true,
+                    ..cache
+                },
+            );
+        }
+        let cache = &self.caches[name];
+        let params = DrawTextureParams {
+            dest_size: Some(cache.rect.size()),
+            flip_y: self.flip_y,
+            ..Default::default()
+        };
+        draw_texture_ex(
+            &cache.target.texture,
+            cache.rect.x,
+            cache.rect.y,
+            WHITE,
+            params,
+        );
+    }
+    /// Like [`Self::cache_layer`], but cached in a grid of view-sized chunks, for huge worlds.
+    ///
+    /// `draw` is called with the world rect of each chunk that needs rendering, and only
+    /// needs to draw what overlaps it, like the tiles of a tilemap in that rect. Chunks
+    /// are rendered as they scroll into view, and dropped once they are more than a
+    /// chunk away from it. Changing the zoom renders all chunks again.
+    pub fn cache_chunked(&mut self, name: &str, mut draw: impl FnMut(Rect)) {
+        let view = self.visible_world_rect();
+        let zoom = self.zoom();
+        let flip_y = self.flip_y;
+        // Taken out while its chunks are rendered with the area's camera settings
+        let mut layer = self
+            .chunked_caches
+            .remove(name)
+            .unwrap_or_else(|| ChunkedLayer {
+                chunk_size: view.size().ceil(),
+                zoom,
+                chunks: HashMap::new(),
+            });
+        if layer.zoom != zoom {
+            layer = ChunkedLayer {
+                chunk_size: view.size().ceil(),
+                zoom,
+                chunks: HashMap::new(),
+            };
+        }
+        let size = layer.chunk_size;
+        let min = (view.point() / size).floor().as_ivec2();
+        let max = ((view.point() + view.size()) / size).floor().as_ivec2();
+        layer.chunks.retain(|key, _| {
+            key.cmpge(min - IVec2::ONE).all() && key.cmple(max + IVec2::ONE).all()
+        });
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let key = ivec2(x, y);
+                let rect = Rect::new(x as f32 * size.x, y as f32 * size.y, size.x, size.y);
+                let px = (size * zoom).ceil().max(Vec2::ONE);
+                let covered = Rect::new(rect.x, rect.y, px.x / zoom, px.y / zoom);
+                let target = layer.chunks.entry(key).or_insert_with(|| {
+                    let target = render_target(px.x as u32, px.y as u32);
+                    target.texture.set_filter(FilterMode::Nearest);
+                    push_camera_state();
+                    set_camera(&self.cache_camera(&target, covered, zoom));
+                    clear_background(BLANK);
+                    draw(rect);
+                    pop_camera_state();
+                    target
+                });
+                let params = DrawTextureParams {
+                    dest_size: Some(covered.size()),
+                    flip_y,
+                    ..Default::default()
+                };
+                draw_texture_ex(&target.texture, rect.x, rect.y, WHITE, params);
+            }
+        }
+        self.chunked_caches.insert(name.to_string(), layer);
+    }
+    /// Camera drawing the world `rect` into all of `target`, at `zoom`.
+    ///
+    /// This uses the same zoom as the area's camera, flip included, so content drawn the
+    /// same way comes out the same. Drawing the target back then needs the same flip.
+    fn cache_camera(&self, target: &RenderTarget, rect: Rect, zoom: f32) -> Camera2D {
+        let size = target.texture.size();
+        Camera2D {
+            target: rect.center(),
+            zoom: self.camera_zoom(size.x as u16, size.y as u16, zoom),
+            render_target: Some(target.clone()),
+            ..Default::default()
+        }
+    }
+    /// Make the next [`Self::cache_layer`] or [`Self::cache_chunked`] call for `name`
+    /// render its content again
+    pub fn invalidate_layer(&mut self, name: &str) {
+        if let Some(cache) = self.caches.get_mut(name) {
+            cache.valid = false;
+        }
+        if let Some(layer) = self.chunked_caches.get_mut(name) {
+            layer.chunks.clear();
+        }
+    }
+    /// Render only the chunks of [`Self::cache_chunked`] layer `name` that overlap `rect`
+    /// again, like after editing a few tiles
+    pub fn invalidate_region(&mut self, name: &str, rect: Rect) {
+        if let Some(layer) = self.chunked_caches.get_mut(name) {
+            let size = layer.chunk_size;
+            layer.chunks.retain(|key, _| {
+                let chunk = Rect::new(key.x as f32 * size.x, key.y as f32 * size.y, size.x, size.y);
+                !chunk.overlaps(&rect)
+            });
+        }
+    }
+}
+
+fn contains_rect(outer: Rect, inner: Rect) -> bool {
+    outer.left() <= inner.left()
+        && outer.top() <= inner.top()
+        && outer.right() >= inner.right()
+        && outer.bottom() >= inner.bottom()
+}
@@ -0,0 +1,225 @@
+//! Camera poses that can be stored and reused.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// Where the camera is looking, and how
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraState {
+    /// World position at the center of the view
+    #[cfg_attr(feature = "serde", serde(with = "vec2_as_array"))]
+    pub target: Vec2,
+    /// Magnification, see [`RenderArea2D::set_zoom`]
+    pub zoom: f32,
+    /// Rotation in degrees
+    pub rotation: f32,
+}
+
+impl Default for CameraState {
+    fn default() -> Self {
+        Self {
+            target: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl CameraState {
+    /// The state `t` of the way from `self` to `other`
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            target: self.target.lerp(other.target, t),
+            zoom: self.zoom + (other.zoom - self.zoom) * t,
+            rotation: self.rotation + (other.rotation - self.rotation) * t,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod vec2_as_array {
+    use macroquad::math::Vec2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(v: &Vec2, s: S) -> Result<S::Ok, S::Error> {
+        v.to_array().serialize(s)
+    }
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec2, D::Error> {
+        <[f32; 2]>::deserialize(d).map(Vec2::from)
+    }
+}
+
+/// How a camera animation progresses over its duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Start slow, then speed up
+    EaseIn,
+    /// Start fast, then slow down
+    EaseOut,
+    /// Start and end slow
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map linear progress `t` from 0 to 1 onto the eased progress
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A running [`RenderArea2D::animate_to`]
+pub(crate) struct CameraAnimation {
+    from: CameraState,
+    to: CameraState,
+    duration: f32,
+    elapsed: f32,
+    easing: Easing,
+}
+
+impl RenderArea2D {
+    /// The current camera target, zoom and rotation
+    pub fn save_state(&self) -> CameraState {
+        CameraState {
+            target: self.camera.target,
+            zoom: self.zoom(),
+            rotation: self.camera.rotation,
+        }
+    }
+    /// Move the camera to `state`, e.g. one from [`Self::save_state`].
+    ///
+    /// This stops a running [`Self::animate_to`].
+    pub fn restore_state(&mut self, state: CameraState) {
+        self.camera_animation = None;
+        self.apply_camera_state(state);
+    }
+    fn apply_camera_state(&mut self, state: CameraState) {
+        self.camera.target = state.target;
+        self.camera.rotation = state.rotation;
+        self.set_zoom(state.zoom);
+    }
+    /// Move the camera from where it is now to `state` over `duration` seconds.
+    ///
+    /// The animation is advanced by [`Self::update`], and replaces any running one.
+    /// Moving the camera yourself in the meantime is overridden on the next update.
+    pub fn animate_to(&mut self, state: CameraState, duration: f32, easing: Easing) {
+        self.camera_animation = Some(CameraAnimation {
+            from: self.save_state(),
+            to: state,
+            duration,
+            elapsed: 0.0,
+            easing,
+        });
+    }
+    /// Whether an [`Self::animate_to`] animation is running
+    pub fn is_animating(&self) -> bool {
+        self.camera_animation.is_some()
+    }
+    /// Stop a running [`Self::animate_to`] where the camera is now
+    pub fn stop_animation(&mut self) {
+        self.camera_animation = None;
+    }
+    pub(crate) fn update_camera_animation(&mut self, dt: f32) {
+        let Some(anim) = &mut self.camera_animation else {
+            return;
+        };
+        anim.elapsed += dt;
+        let t = if anim.duration > 0.0 {
+            anim.elapsed / anim.duration
+        } else {
+            1.0
+        };
+        let state = anim.from.lerp(anim.to, anim.easing.apply(t));
+        if t >= 1.0 {
+            self.camera_animation = None;
+        }
+        self.apply_camera_state(state);
+    }
+
+    /// Render `draw` into a new `size` texture, as seen from `pose`.
+    ///
+    /// The thumbnail shows the same part of the world the area would at that pose,
+    /// just at a different resolution, which is handy for live level previews.
+    /// The area itself and the active camera are left untouched.
+    pub fn render_thumbnail(
+        &self,
+        pose: CameraState,
+        size: (u16, u16),
+        draw: impl FnOnce(),
+    ) -> Texture2D {
+        let rt = render_target(size.0.into(), size.1.into());
+        push_camera_state();
+        set_camera(&Camera2D {
+            target: pose.target,
+            zoom: vec2(2.0 / f32::from(self.width), 2.0 / f32::from(self.height)) * pose.zoom,
+            rotation: pose.rotation,
+            render_target: Some(rt.clone()),
+            ..Default::default()
+        });
+        clear_background(BLANK);
+        draw();
+        pop_camera_state();
+        rt.texture
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easing_ends() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(-1.0), 0.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn easing_shape() {
+        assert_eq!(Easing::Linear.apply(0.25), 0.25);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn camera_state_lerp() {
+        let a = CameraState {
+            target: vec2(0.0, 10.0),
+            zoom: 1.0,
+            rotation: 0.0,
+        };
+        let b = CameraState {
+            target: vec2(100.0, 30.0),
+            zoom: 3.0,
+            rotation: 90.0,
+        };
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(
+            a.lerp(b, 0.5),
+            CameraState {
+                target: vec2(50.0, 20.0),
+                zoom: 2.0,
+                rotation: 45.0,
+            }
+        );
+    }
+}
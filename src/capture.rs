@@ -0,0 +1,23 @@
+//! Saving captured frames as PNG files.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+use std::path::Path;
+
+impl RenderArea2D {
+    /// Save the render area as a PNG file at virtual resolution, see [`Self::capture`]
+    pub fn save_png(&self, path: impl AsRef<Path>) -> image::ImageResult<()> {
+        save_png(&self.capture(), path.as_ref())
+    }
+}
+
+/// Save an image read back from a render target, which is already top-down
+pub(crate) fn save_png(img: &Image, path: &Path) -> image::ImageResult<()> {
+    image::save_buffer(
+        path,
+        &img.bytes,
+        u32::from(img.width),
+        u32::from(img.height),
+        image::ColorType::Rgba8,
+    )
+}
@@ -0,0 +1,19 @@
+//! Copying frames to the OS clipboard.
+
+use crate::RenderArea2D;
+use std::borrow::Cow;
+
+impl RenderArea2D {
+    /// Put the current contents of the render area on the OS clipboard, at virtual resolution.
+    ///
+    /// Handy for letting playtesters paste screenshots into bug reports.
+    /// The frame comes from [`Self::capture`].
+    pub fn capture_to_clipboard(&self) -> Result<(), arboard::Error> {
+        let img = self.capture();
+        arboard::Clipboard::new()?.set_image(arboard::ImageData {
+            width: usize::from(img.width),
+            height: usize::from(img.height),
+            bytes: Cow::Owned(img.bytes),
+        })
+    }
+}
@@ -0,0 +1,186 @@
+//! Presenting the final texture to the window.
+
+use crate::{shader, LayerBlend, PassAlpha};
+use macroquad::prelude::*;
+use std::cell::OnceCell;
+
+/// Presentation shader, specialized with `PREMULTIPLIED` and `MASKED` defines
+const BLIT: &str = r#"
+precision mediump float;
+varying vec4 color;
+varying vec2 uv;
+uniform sampler2D Texture;
+uniform sampler2D Mask;
+uniform vec4 MaskRect;
+uniform vec4 MaskSource;
+uniform float Gamma;
+
+void main() {
+    vec4 c = color * texture2D(Texture, uv);
+#ifdef MASKED
+    vec2 mask_uv = MaskSource.xy + (uv - MaskRect.xy) / MaskRect.zw * MaskSource.zw;
+    float mask = texture2D(Mask, mask_uv).a;
+#endif
+#ifdef PREMULTIPLIED
+    if (c.a > 0.0) {
+        c.rgb = pow(c.rgb / c.a, vec3(Gamma)) * c.a;
+    }
+#ifdef MASKED
+    c *= mask;
+#endif
+#else
+    c.rgb = pow(c.rgb, vec3(Gamma));
+#ifdef MASKED
+    c.a *= mask;
+#endif
+#endif
+    gl_FragColor = c;
+}
+"#;
+
+/// How colors are stored in the render target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// Colors are sRGB all the way through, like plain macroquad
+    #[default]
+    Srgb,
+    /// Colors are linear inside the render target and gamma-encoded when presented.
+    ///
+    /// Alpha blending in linear space keeps overlapping translucent sprites from
+    /// darkening. Draw with colors converted by [`ColorSpace::to_internal`];
+    /// textures are still sampled as-is.
+    /// The target only has 8 bits per channel, so very dark gradients may band.
+    LinearInternal,
+}
+
+impl ColorSpace {
+    const GAMMA: f32 = 2.2;
+    /// Convert an sRGB color to the color space used inside the render target
+    pub fn to_internal(self, color: Color) -> Color {
+        match self {
+            Self::Srgb => color,
+            Self::LinearInternal => Color::new(
+                color.r.powf(Self::GAMMA),
+                color.g.powf(Self::GAMMA),
+                color.b.powf(Self::GAMMA),
+                color.a,
+            ),
+        }
+    }
+    fn encode_exponent(self) -> f32 {
+        match self {
+            Self::Srgb => 1.0,
+            Self::LinearInternal => Self::GAMMA.recip(),
+        }
+    }
+}
+
+/// Materials for the presentation variants that plain `draw_texture_ex` can't do,
+/// indexed by `additive * 4 + premultiplied * 2 + masked`
+pub(crate) struct BlitMaterials([Material; 8]);
+
+impl BlitMaterials {
+    fn new() -> Self {
+        Self(std::array::from_fn(|i| {
+            let (additive, premultiplied, masked) = (i >= 4, i % 4 >= 2, i % 2 == 1);
+            let mut fragment = String::from("#version 100\n");
+            if premultiplied {
+                fragment.push_str("#define PREMULTIPLIED\n");
+            }
+            if masked {
+                fragment.push_str("#define MASKED\n");
+            }
+            fragment.push_str(BLIT);
+            let color_blend = match (additive, premultiplied) {
+                (false, false) => shader::alpha_blend(),
+                (false, true) => shader::premultiplied_blend(),
+                (true, false) => shader::additive_blend(),
+                (true, true) => shader::premultiplied_additive_blend(),
+            };
+            shader::load(
+                &fragment,
+                MaterialParams {
+                    pipeline_params: PipelineParams {
+                        color_blend,
+                        ..Default::default()
+                    },
+                    uniforms: vec![
+                        UniformDesc::new("Gamma", UniformType::Float1),
+                        UniformDesc::new("MaskRect", UniformType::Float4),
+                        UniformDesc::new("MaskSource", UniformType::Float4),
+                    ],
+                    textures: if masked {
+                        vec!["Mask".to_string()]
+                    } else {
+                        vec![]
+                    },
+                },
+            )
+        }))
+    }
+}
+
+/// A mask for [`blit`], and how it lines up with the presented texture
+#[derive(Clone, Copy)]
+pub(crate) struct Mask<'a> {
+    pub(crate) texture: &'a Texture2D,
+    /// Part of the presented texture the mask is stretched over, in its texels
+    pub(crate) dest: Rect,
+    /// Part of the mask texture that is used, in its texels
+    pub(crate) source: Rect,
+}
+
+/// How a texture is presented by [`blit`]
+pub(crate) struct BlitOptions<'a> {
+    pub(crate) alpha: PassAlpha,
+    pub(crate) mask: Option<Mask<'a>>,
+    pub(crate) color_space: ColorSpace,
+    pub(crate) tint: Color,
+    pub(crate) blend: LayerBlend,
+}
+
+/// Draw `tex` at `pos` in window space, handling its alpha convention, color space,
+/// blending and an optional mask.
+///
+/// The materials are only created once something other than a plain draw is needed.
+pub(crate) fn blit(
+    tex: &Texture2D,
+    pos: Vec2,
+    params: DrawTextureParams,
+    opts: &BlitOptions,
+    materials: &OnceCell<BlitMaterials>,
+) {
+    let premultiplied = opts.alpha == PassAlpha::Premultiplied;
+    let additive = opts.blend == LayerBlend::Additive;
+    let plain = !premultiplied && !additive && opts.mask.is_none();
+    let material = (!plain || opts.color_space != ColorSpace::Srgb).then(|| {
+        let index = usize::from(additive) * 4
+            + usize::from(premultiplied) * 2
+            + usize::from(opts.mask.is_some());
+        let material = &materials.get_or_init(BlitMaterials::new).0[index];
+        material.set_uniform("Gamma", opts.color_space.encode_exponent());
+        material
+    });
+    if let Some(material) = material {
+        if let Some(mask) = opts.mask {
+            material.set_uniform("MaskRect", uv_rect(mask.dest, tex.size()));
+            material.set_uniform("MaskSource", uv_rect(mask.source, mask.texture.size()));
+            material.set_texture("Mask", mask.texture.clone());
+        }
+        gl_use_material(material);
+    }
+    draw_texture_ex(tex, pos.x, pos.y, opts.tint, params);
+    if material.is_some() {
+        gl_use_default_material();
+    }
+}
+
+/// `rect` in texels of a `size` texture, as x, y, w, h in texture coordinates
+fn uv_rect(rect: Rect, size: Vec2) -> Vec4 {
+    vec4(
+        rect.x / size.x,
+        rect.y / size.y,
+        rect.w / size.x,
+        rect.h / size.y,
+    )
+}
@@ -0,0 +1,79 @@
+//! Pausing presentation and stepping it frame by frame, for debugging.
+
+use crate::{memory::texture_bytes, pixel_camera, RenderArea2D};
+use macroquad::prelude::*;
+use std::cell::Cell;
+
+pub(crate) struct DebugStep {
+    /// Copy of the render target as it was on the presented frame
+    frozen: RenderTarget,
+    /// Effect time and frame number the presented frame is rendered with
+    pub(crate) time: Cell<f64>,
+    pub(crate) frame: Cell<u32>,
+    /// Set by [`RenderArea2D::debug_step`], consumed by the next update and draw
+    pub(crate) step_update: bool,
+    step_draw: Cell<bool>,
+}
+
+impl DebugStep {
+    /// The texture to present, taking a new snapshot of `src` if stepping
+    pub(crate) fn source(&self, src: &Texture2D, time: f64, frame: u32) -> Texture2D {
+        if self.step_draw.take() {
+            copy(src, &self.frozen);
+            self.time.set(time);
+            self.frame.set(frame);
+        }
+        self.frozen.texture.clone()
+    }
+    pub(crate) fn gpu_bytes(&self) -> usize {
+        texture_bytes(&self.frozen.texture)
+    }
+}
+
+fn copy(src: &Texture2D, dst: &RenderTarget) {
+    push_camera_state();
+    set_camera(&pixel_camera(dst, src.width() as u16, src.height() as u16));
+    clear_background(BLANK);
+    draw_texture(src, 0.0, 0.0, WHITE);
+    pop_camera_state();
+}
+
+impl RenderArea2D {
+    /// Freeze presentation on the current contents of the render area.
+    ///
+    /// [`Self::draw`] keeps presenting that frame with the same effect time, and
+    /// [`Self::update`] stops advancing effects like pulses, subtitles and toasts, until
+    /// [`Self::debug_resume`].
+    /// Use [`Self::debug_step`] to advance one frame at a time.
+    pub fn debug_pause(&mut self) {
+        if self.debug_step.is_some() {
+            return;
+        }
+        let (w, h) = self.alloc_size();
+        let frozen = render_target(w.into(), h.into());
+        frozen.texture.set_filter(FilterMode::Nearest);
+        copy(&self.render_target.texture, &frozen);
+        self.debug_step = Some(DebugStep {
+            frozen,
+            time: Cell::new(self.window_focus.effect_time()),
+            frame: Cell::new(self.frame_count.get()),
+            step_update: false,
+            step_draw: Cell::new(false),
+        });
+    }
+    /// Resume normal presentation after [`Self::debug_pause`]
+    pub fn debug_resume(&mut self) {
+        self.debug_step = None;
+    }
+    /// While paused, let the next [`Self::update`] and [`Self::draw`] run for one frame
+    pub fn debug_step(&mut self) {
+        if let Some(step) = &mut self.debug_step {
+            step.step_update = true;
+            step.step_draw.set(true);
+        }
+    }
+    /// Whether presentation is paused with [`Self::debug_pause`]
+    pub fn is_debug_paused(&self) -> bool {
+        self.debug_step.is_some()
+    }
+}
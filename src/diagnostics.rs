@@ -0,0 +1,68 @@
+//! Reporting of noteworthy render area events to the game.
+
+use macroquad::prelude::*;
+
+/// Something noteworthy that happened to a render area
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RenderAreaEvent {
+    /// The presented scale changed, in any [`crate::ScaleMode`].
+    ///
+    /// Scales are window pixels per virtual pixel, see
+    /// [`crate::RenderArea2D::presented_scale`]; the smaller axis under
+    /// [`crate::ScaleMode::Stretch`].
+    ScaleChanged {
+        /// Scale before the change
+        old: f32,
+        /// Scale after the change
+        new: f32,
+    },
+    /// The camera became NaN or infinite, and was reset to its last valid state
+    CameraSanitized {
+        /// The bad camera target
+        bad_target: Vec2,
+    },
+    /// The render target was made again, e.g. for a new size or after a lost context.
+    /// Its contents are lost.
+    TargetRecreated {
+        /// Allocated width of the new target, in pixels
+        width: u16,
+        /// Allocated height of the new target, in pixels
+        height: u16,
+    },
+    /// Saving a captured frame failed
+    CaptureFailed {
+        /// Where the frame was supposed to go
+        path: std::path::PathBuf,
+        /// What went wrong
+        error: String,
+    },
+}
+
+/// Receives [`RenderAreaEvent`]s, see [`crate::RenderArea2D::set_diagnostics`]
+pub trait DiagnosticsSink {
+    /// Called whenever an event happens
+    fn event(&self, event: RenderAreaEvent);
+}
+
+impl<F: Fn(RenderAreaEvent)> DiagnosticsSink for F {
+    fn event(&self, event: RenderAreaEvent) {
+        self(event)
+    }
+}
+
+/// A [`DiagnosticsSink`] that forwards events to the `log` crate
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogSink;
+
+#[cfg(feature = "log")]
+impl DiagnosticsSink for LogSink {
+    fn event(&self, event: RenderAreaEvent) {
+        match event {
+            RenderAreaEvent::CameraSanitized { .. } => log::warn!("{event:?}"),
+            RenderAreaEvent::CaptureFailed { .. } => log::error!("{event:?}"),
+            _ => log::debug!("{event:?}"),
+        }
+    }
+}
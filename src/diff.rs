@@ -0,0 +1,92 @@
+//! Per-frame dirty region diffs, for streaming the virtual frame to spectators.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// A changed part of the frame, see [`RenderArea2D::frame_diff`]
+#[derive(Debug, Clone)]
+pub struct DirtyRegion {
+    /// Left edge of the region, in virtual pixels
+    pub x: u16,
+    /// Top edge of the region, in virtual pixels
+    pub y: u16,
+    /// The new pixels of the region, top-down RGBA
+    pub image: Image,
+}
+
+/// What changed in the render area since the last [`RenderArea2D::frame_diff`]
+#[derive(Debug, Clone)]
+pub struct FrameDiff {
+    /// Size of the whole frame in virtual pixels
+    pub size: (u16, u16),
+    /// Changed regions, which don't overlap. Applying them in any order gives the new frame.
+    pub regions: Vec<DirtyRegion>,
+}
+
+impl RenderArea2D {
+    /// Compare the render area with how it was on the last call, in `tile` by `tile` blocks.
+    ///
+    /// Changed tiles next to each other in a row are merged into one region. The first
+    /// call, and the first after resizing, returns the whole frame. It uses
+    /// [`Self::capture`], but for low resolutions it's fine to do every frame.
+    pub fn frame_diff(&mut self, tile: u16) -> FrameDiff {
+        let tile = tile.max(1);
+        let img = self.capture();
+        let (w, h) = (img.width, img.height);
+        let prev = self
+            .diff_prev
+            .take()
+            .filter(|p| (p.width, p.height) == (w, h));
+        let mut regions = Vec::new();
+        match &prev {
+            None => regions.push(DirtyRegion {
+                x: 0,
+                y: 0,
+                image: img.clone(),
+            }),
+            Some(prev) => {
+                for ty in (0..h).step_by(tile.into()) {
+                    let th = tile.min(h - ty);
+                    // Start of the current run of dirty tiles in this row
+                    let mut run: Option<u16> = None;
+                    for tx in (0..w).step_by(tile.into()).chain([w]) {
+                        let dirty =
+                            tx < w && tile_differs(&img, prev, tx, ty, tile.min(w - tx), th);
+                        match (run, dirty) {
+                            (None, true) => run = Some(tx),
+                            (Some(start), false) => {
+                                let rect = Rect::new(
+                                    f32::from(start),
+                                    f32::from(ty),
+                                    f32::from(tx - start),
+                                    f32::from(th),
+                                );
+                                regions.push(DirtyRegion {
+                                    x: start,
+                                    y: ty,
+                                    image: img.sub_image(rect),
+                                });
+                                run = None;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        self.diff_prev = Some(img);
+        FrameDiff {
+            size: (w, h),
+            regions,
+        }
+    }
+}
+
+fn tile_differs(a: &Image, b: &Image, x: u16, y: u16, w: u16, h: u16) -> bool {
+    let stride = usize::from(a.width) * 4;
+    (usize::from(y)..usize::from(y + h)).any(|row| {
+        let start = row * stride + usize::from(x) * 4;
+        let end = start + usize::from(w) * 4;
+        a.bytes[start..end] != b.bytes[start..end]
+    })
+}
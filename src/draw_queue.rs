@@ -0,0 +1,106 @@
+//! Retained sprite drawing with layer and y sorting.
+
+use crate::RenderArea2D;
+use macroquad::models::{draw_mesh, Mesh, Vertex};
+use macroquad::prelude::*;
+
+pub(crate) struct QueuedSprite {
+    texture: Texture2D,
+    pos: Vec2,
+    color: Color,
+    params: DrawTextureParams,
+    layer: i32,
+    y_sort: f32,
+}
+
+impl RenderArea2D {
+    /// Queue a sprite to be drawn by the next [`Self::flush`].
+    ///
+    /// Sprites are drawn by `layer` first, lowest at the bottom, then by `y_sort`,
+    /// lowest first. Usually `y_sort` is the y of the sprite's feet, so things further
+    /// down the screen are drawn in front. Ties keep the order they were queued in.
+    pub fn queue_sprite(
+        &self,
+        texture: &Texture2D,
+        pos: impl Into<Vec2>,
+        color: Color,
+        params: DrawTextureParams,
+        layer: i32,
+        y_sort: f32,
+    ) {
+        self.draw_queue.borrow_mut().push(QueuedSprite {
+            texture: texture.clone(),
+            pos: pos.into(),
+            color,
+            params,
+            layer,
+            y_sort,
+        });
+    }
+    /// Sort and draw all sprites queued with [`Self::queue_sprite`], emptying the queue.
+    ///
+    /// Call this while the area is [set](Self::set). Immediate drawing done after
+    /// this ends up in front of the sprites. Consecutive sprites with the same texture
+    /// are batched into one mesh, so dense scenes take few draw calls.
+    pub fn flush(&self) {
+        let mut queue = self.draw_queue.take();
+        queue.sort_by(|a, b| a.layer.cmp(&b.layer).then(a.y_sort.total_cmp(&b.y_sort)));
+        let mut rest = &queue[..];
+        while let Some(first) = rest.first() {
+            let run = rest
+                .iter()
+                .take(MAX_BATCH)
+                .take_while(|s| s.texture == first.texture)
+                .count();
+            let (batch, tail) = rest.split_at(run);
+            rest = tail;
+            let mut mesh = Mesh {
+                vertices: Vec::with_capacity(batch.len() * 4),
+                indices: Vec::with_capacity(batch.len() * 6),
+                texture: Some(first.texture.clone()),
+            };
+            for sprite in batch {
+                let base = mesh.vertices.len() as u16;
+                mesh.vertices.extend(sprite.vertices());
+                mesh.indices.extend([0, 1, 2, 0, 2, 3].map(|i| base + i));
+            }
+            draw_mesh(&mesh);
+        }
+    }
+}
+
+/// Most sprites in one mesh, staying under macroquad's per-draw index limit
+const MAX_BATCH: usize = 800;
+
+impl QueuedSprite {
+    /// Corners of the sprite, laid out the same way as `draw_texture_ex`
+    fn vertices(&self) -> [Vertex; 4] {
+        let p = &self.params;
+        let tex_size = self.texture.size();
+        let src = p
+            .source
+            .unwrap_or(Rect::new(0.0, 0.0, tex_size.x, tex_size.y));
+        let mut size = p.dest_size.unwrap_or(src.size());
+        let mut pos = self.pos;
+        if p.flip_x {
+            pos.x += size.x;
+            size.x = -size.x;
+        }
+        if p.flip_y {
+            pos.y += size.y;
+            size.y = -size.y;
+        }
+        let pivot = p.pivot.unwrap_or(pos + size / 2.0);
+        let rot = Vec2::from_angle(p.rotation);
+        let corner = |offset: Vec2| pivot + rot.rotate(pos + offset - pivot);
+        let uv0 = src.point() / tex_size;
+        let uv1 = (src.point() + src.size()) / tex_size;
+        let vert = |at: Vec2, u: f32, v: f32| Vertex::new(at.x, at.y, 0.0, u, v, self.color);
+        [
+            vert(corner(Vec2::ZERO), uv0.x, uv0.y),
+            vert(corner(vec2(size.x, 0.0)), uv1.x, uv0.y),
+            vert(corner(size), uv1.x, uv1.y),
+            vert(corner(vec2(0.0, size.y)), uv0.x, uv1.y),
+        ]
+    }
+}
@@ -0,0 +1,249 @@
+//! User-definable post-processing passes, run on the render target before presenting it.
+
+use crate::{memory::texture_bytes, pixel_camera, shader};
+use macroquad::prelude::*;
+
+/// How the color of a texture relates to its alpha
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PassAlpha {
+    /// Color is independent of alpha. This is what the render area itself contains.
+    #[default]
+    Straight,
+    /// Color is already multiplied by alpha.
+    ///
+    /// Filtering and blending premultiplied textures doesn't produce dark halos
+    /// around translucent edges.
+    Premultiplied,
+}
+
+/// State of the render area, passed to every [`EffectPass`]
+#[derive(Debug, Clone, Copy)]
+pub struct PassCtx {
+    /// Virtual resolution of the render area. All pass targets are this size.
+    pub size: Vec2,
+    /// Scale the result is going to be presented at
+    pub scale: f32,
+    /// Time since program start, in seconds
+    pub time: f32,
+    /// Number of frames presented so far
+    pub frame: u32,
+    /// World position the camera is centered on
+    pub camera_target: Vec2,
+    /// How far the camera target moved since the previous frame
+    pub camera_delta: Vec2,
+}
+
+impl PassCtx {
+    /// Set `dst` as the drawing target, with one unit per virtual pixel
+    pub fn set_target(&self, dst: &RenderTarget) {
+        set_camera(&pixel_camera(dst, self.size.x as u16, self.size.y as u16));
+    }
+}
+
+/// A post-processing pass, see [`crate::RenderArea2D::add_pass`]
+pub trait EffectPass {
+    /// Render `src` into `dst`, which is not cleared beforehand.
+    ///
+    /// Use [`PassCtx::set_target`] to start drawing into `dst`. Passes can keep their
+    /// own extra render targets for multi-step effects.
+    fn apply(&mut self, src: Texture2D, dst: &RenderTarget, ctx: &PassCtx);
+    /// Alpha convention this pass expects `src` to be in.
+    ///
+    /// The chain converts between conventions as needed.
+    fn input_alpha(&self) -> PassAlpha {
+        PassAlpha::Straight
+    }
+    /// Alpha convention this pass writes into `dst`
+    fn output_alpha(&self) -> PassAlpha {
+        PassAlpha::Straight
+    }
+    /// The material anchors registered with [`crate::RenderArea2D::anchor_uniform`]
+    /// are written to.
+    ///
+    /// Passes without one can't use anchors.
+    fn material(&self) -> Option<&Material> {
+        None
+    }
+    /// Bytes of GPU memory held by the pass's own render targets and textures,
+    /// counted by [`crate::RenderArea2D::gpu_memory_estimate`]
+    fn gpu_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// Uniforms set automatically on materials added with
+/// [`crate::RenderArea2D::add_post_effect`].
+///
+/// Include these in the material's `MaterialParams::uniforms`:
+/// - `Resolution` (`vec2`): virtual resolution, [`PassCtx::size`]
+/// - `Time` (`float`): [`PassCtx::time`]
+/// - `Scale` (`float`): [`PassCtx::scale`]
+/// - `CameraTarget` (`vec2`): [`PassCtx::camera_target`]
+/// - `CameraDelta` (`vec2`): [`PassCtx::camera_delta`], for parallaxing backgrounds
+pub fn post_uniforms() -> Vec<UniformDesc> {
+    vec![
+        UniformDesc::new("Resolution", UniformType::Float2),
+        UniformDesc::new("Time", UniformType::Float1),
+        UniformDesc::new("Scale", UniformType::Float1),
+        UniformDesc::new("CameraTarget", UniformType::Float2),
+        UniformDesc::new("CameraDelta", UniformType::Float2),
+    ]
+}
+
+/// A pass drawing its source through a user material
+pub(crate) struct MaterialPass(pub(crate) Material);
+
+impl EffectPass for MaterialPass {
+    fn apply(&mut self, src: Texture2D, dst: &RenderTarget, ctx: &PassCtx) {
+        let material = &self.0;
+        material.set_uniform("Resolution", ctx.size);
+        material.set_uniform("Time", ctx.time);
+        material.set_uniform("Scale", ctx.scale);
+        material.set_uniform("CameraTarget", ctx.camera_target);
+        material.set_uniform("CameraDelta", ctx.camera_delta);
+        ctx.set_target(dst);
+        clear_background(BLANK);
+        gl_use_material(material);
+        draw_texture(&src, 0.0, 0.0, WHITE);
+        gl_use_default_material();
+    }
+    fn material(&self) -> Option<&Material> {
+        Some(&self.0)
+    }
+}
+
+const PREMULTIPLY: &str = r#"#version 100
+precision mediump float;
+varying vec2 uv;
+uniform sampler2D Texture;
+
+void main() {
+    vec4 c = texture2D(Texture, uv);
+    gl_FragColor = vec4(c.rgb * c.a, c.a);
+}
+"#;
+
+const UNPREMULTIPLY: &str = r#"#version 100
+precision mediump float;
+varying vec2 uv;
+uniform sampler2D Texture;
+
+void main() {
+    vec4 c = texture2D(Texture, uv);
+    gl_FragColor = c.a > 0.0 ? vec4(c.rgb / c.a, c.a) : vec4(0.0);
+}
+"#;
+
+/// Materials for converting between alpha conventions
+pub(crate) struct AlphaMaterials {
+    premultiply: Material,
+    unpremultiply: Material,
+}
+
+impl AlphaMaterials {
+    fn new() -> Self {
+        Self {
+            premultiply: shader::load(PREMULTIPLY, MaterialParams::default()),
+            unpremultiply: shader::load(UNPREMULTIPLY, MaterialParams::default()),
+        }
+    }
+    /// Overwrite `dst` with `src` converted to the `to` convention
+    fn convert(&self, src: &Texture2D, dst: &RenderTarget, to: PassAlpha, ctx: &PassCtx) {
+        ctx.set_target(dst);
+        gl_use_material(match to {
+            PassAlpha::Straight => &self.unpremultiply,
+            PassAlpha::Premultiplied => &self.premultiply,
+        });
+        draw_texture(src, 0.0, 0.0, WHITE);
+        gl_use_default_material();
+    }
+}
+
+pub(crate) struct Entry {
+    pub(crate) name: String,
+    pub(crate) enabled: bool,
+    pub(crate) pass: Box<dyn EffectPass>,
+    /// Uniform names and providers of world positions, uploaded as UVs before each run
+    pub(crate) anchors: Vec<(String, Box<dyn Fn() -> Vec2>)>,
+}
+
+pub(crate) struct Chain {
+    pub(crate) passes: Vec<Entry>,
+    targets: [RenderTarget; 2],
+    /// Created on first use, since most chains never need them
+    alpha: Option<AlphaMaterials>,
+}
+
+fn targets(width: u16, height: u16) -> [RenderTarget; 2] {
+    std::array::from_fn(|_| {
+        let rt = render_target(width.into(), height.into());
+        rt.texture.set_filter(FilterMode::Nearest);
+        rt
+    })
+}
+
+impl Chain {
+    pub(crate) fn new(width: u16, height: u16) -> Self {
+        Self {
+            passes: Vec::new(),
+            targets: targets(width, height),
+            alpha: None,
+        }
+    }
+    /// Recreate the pass targets for a new virtual resolution, keeping the passes
+    pub(crate) fn resize(&mut self, width: u16, height: u16) {
+        self.targets = targets(width, height);
+    }
+    pub(crate) fn gpu_bytes(&self) -> usize {
+        let targets: usize = self
+            .targets
+            .iter()
+            .map(|rt| texture_bytes(&rt.texture))
+            .sum();
+        targets
+            + self
+                .passes
+                .iter()
+                .map(|e| e.pass.gpu_bytes())
+                .sum::<usize>()
+    }
+    /// Run all enabled passes on `src`, and return the texture with the result,
+    /// along with its alpha convention.
+    ///
+    /// Disabled passes are skipped entirely, so with no enabled passes this is just `src`.
+    pub(crate) fn run(
+        &mut self,
+        src: &Texture2D,
+        ctx: &PassCtx,
+        world_to_uv: impl Fn(Vec2) -> Vec2,
+    ) -> (Texture2D, PassAlpha) {
+        let mut src = src.clone();
+        let mut alpha = PassAlpha::Straight;
+        // Index of the next target to write to, alternating so a step never reads its output
+        let mut step = 0;
+        push_camera_state();
+        for entry in self.passes.iter_mut().filter(|e| e.enabled) {
+            if let Some(material) = entry.pass.material() {
+                for (uniform, provider) in &entry.anchors {
+                    material.set_uniform(uniform, world_to_uv(provider()));
+                }
+            }
+            let wanted = entry.pass.input_alpha();
+            if wanted != alpha {
+                let dst = &self.targets[step % 2];
+                self.alpha
+                    .get_or_insert_with(AlphaMaterials::new)
+                    .convert(&src, dst, wanted, ctx);
+                src = dst.texture.clone();
+                step += 1;
+            }
+            let dst = &self.targets[step % 2];
+            entry.pass.apply(src, dst, ctx);
+            src = dst.texture.clone();
+            alpha = entry.pass.output_alpha();
+            step += 1;
+        }
+        pop_camera_state();
+        (src, alpha)
+    }
+}
@@ -0,0 +1,87 @@
+//! Smoothly following a target with the camera.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// How the camera follows a target, see [`RenderArea2D::follow`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraFollow {
+    /// Area around the camera center, in world units, where the target can move
+    /// without the camera moving
+    pub deadzone: Rect,
+    /// How quickly the camera catches up, per second. 0 snaps to the target right away.
+    pub smoothing: f32,
+    /// Maximum camera speed in world units per second, if any
+    pub max_speed: Option<f32>,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            deadzone: Rect::new(0.0, 0.0, 0.0, 0.0),
+            smoothing: 8.0,
+            max_speed: None,
+        }
+    }
+}
+
+impl CameraFollow {
+    /// A follow config with a `w` by `h` deadzone centered on the camera
+    pub fn with_deadzone(w: f32, h: f32) -> Self {
+        Self {
+            deadzone: Rect::new(-w / 2.0, -h / 2.0, w, h),
+            ..Default::default()
+        }
+    }
+    /// How far the camera at `camera` should move this frame to follow `target`
+    fn step(&self, camera: Vec2, target: Vec2, dt: f32) -> Vec2 {
+        let offset = target - camera;
+        let dz = self.deadzone;
+        let outside = |off: f32, min: f32, max: f32| {
+            if off < min {
+                off - min
+            } else if off > max {
+                off - max
+            } else {
+                0.0
+            }
+        };
+        let wanted = vec2(
+            outside(offset.x, dz.left(), dz.right()),
+            outside(offset.y, dz.top(), dz.bottom()),
+        );
+        let amount = if self.smoothing > 0.0 {
+            1.0 - (-self.smoothing * dt).exp()
+        } else {
+            1.0
+        };
+        let step = wanted * amount;
+        match self.max_speed {
+            Some(speed) => step.clamp_length_max(speed * dt),
+            None => step,
+        }
+    }
+}
+
+impl RenderArea2D {
+    /// Set how [`Self::follow`] moves the camera
+    pub fn set_camera_follow(&mut self, follow: CameraFollow) {
+        self.camera_follow = follow;
+    }
+    /// Move the camera toward `target` by one frame of `dt` seconds.
+    ///
+    /// Call this every frame while following something. The camera only moves once the
+    /// target leaves the deadzone, easing in with the configured smoothing. The camera can
+    /// still be moved manually in between, following just continues from wherever it is.
+    /// While a [`crate::PanZoomController`] is dragging the camera, this does nothing.
+    pub fn follow(&mut self, target: impl Into<Vec2>, dt: f32) {
+        if self.manual_pan {
+            return;
+        }
+        let step = self
+            .camera_follow
+            .step(self.camera.target, target.into(), dt);
+        self.camera.target += step;
+        self.clamp_camera();
+    }
+}
@@ -0,0 +1,120 @@
+//! A tiny built-in 3x5 pixel font for debug text, no assets needed.
+
+use macroquad::prelude::*;
+
+const GLYPH_W: f32 = 3.0;
+const GLYPH_H: f32 = 5.0;
+/// Horizontal distance between glyph origins, in font pixels
+const ADVANCE: f32 = GLYPH_W + 1.0;
+/// Vertical distance between lines, in font pixels
+const LINE_HEIGHT: f32 = GLYPH_H + 1.0;
+
+/// Rows of a glyph from top to bottom, with the leftmost pixel in bit 2
+fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c.to_ascii_uppercase() {
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        ';' => [0b000, 0b010, 0b000, 0b010, 0b100],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '?' => [0b110, 0b001, 0b010, 0b000, 0b010],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '*' => [0b000, 0b101, 0b010, 0b101, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        '[' => [0b011, 0b010, 0b010, 0b010, 0b011],
+        ']' => [0b110, 0b010, 0b010, 0b010, 0b110],
+        '<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+        '>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        '"' => [0b101, 0b101, 0b000, 0b000, 0b000],
+        _ => return None,
+    })
+}
+
+/// Draw `text` with the built-in 3x5 pixel font, top-left corner at (`x`, `y`).
+///
+/// Each font pixel is `scale` units big. Supports digits, latin letters (drawn as
+/// uppercase), common punctuation and newlines. Unknown characters are drawn as `?`.
+pub fn draw_pixel_text(text: &str, x: f32, y: f32, scale: f32, color: Color) {
+    let (mut cx, mut cy) = (x, y);
+    for c in text.chars() {
+        if c == '\n' {
+            cx = x;
+            cy += LINE_HEIGHT * scale;
+            continue;
+        }
+        let rows = glyph(c).or_else(|| glyph('?')).unwrap_or_default();
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) != 0 {
+                    draw_rectangle(
+                        cx + col as f32 * scale,
+                        cy + row as f32 * scale,
+                        scale,
+                        scale,
+                        color,
+                    );
+                }
+            }
+        }
+        cx += ADVANCE * scale;
+    }
+}
+
+/// The size `text` takes up when drawn with [`draw_pixel_text`]
+pub fn measure_pixel_text(text: &str, scale: f32) -> Vec2 {
+    let mut lines = 0;
+    let mut longest = 0;
+    for line in text.split('\n') {
+        lines += 1;
+        longest = longest.max(line.chars().count());
+    }
+    vec2(
+        (longest as f32 * ADVANCE - 1.0).max(0.0) * scale,
+        (lines as f32 * LINE_HEIGHT - 1.0) * scale,
+    )
+}
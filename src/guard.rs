@@ -0,0 +1,33 @@
+//! Setting the area's camera for a scope.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// Keeps the camera of a render area set until dropped, see [`RenderArea2D::begin`]
+#[must_use = "the previous camera is restored as soon as the guard is dropped"]
+pub struct AreaGuard {
+    _private: (),
+}
+
+impl Drop for AreaGuard {
+    fn drop(&mut self) {
+        pop_camera_state();
+    }
+}
+
+impl RenderArea2D {
+    /// [Set](Self::set) the area until the returned guard is dropped, then restore
+    /// whatever camera was active before.
+    ///
+    /// Guards nest, so drawing into one area while another is set works as expected.
+    pub fn begin(&self) -> AreaGuard {
+        push_camera_state();
+        self.set();
+        AreaGuard { _private: () }
+    }
+    /// Run `draw` with the area [set](Self::set), restoring the previous camera afterwards
+    pub fn with<R>(&self, draw: impl FnOnce() -> R) -> R {
+        let _guard = self.begin();
+        draw()
+    }
+}
@@ -0,0 +1,78 @@
+//! Immediate-mode hover tracking for world-space rectangles.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// A change in hover state, see [`RenderArea2D::track_hover`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HoverEvent {
+    /// The mouse started hovering the rect
+    Enter {
+        /// Id the rect was tracked with
+        id: u64,
+        /// Mouse position relative to the rect's top-left corner
+        local: Vec2,
+    },
+    /// The mouse moved while hovering the rect
+    Move {
+        /// Id the rect was tracked with
+        id: u64,
+        /// Mouse position relative to the rect's top-left corner
+        local: Vec2,
+    },
+    /// The mouse stopped hovering the rect, or the rect wasn't tracked last frame
+    Leave {
+        /// Id the rect was tracked with
+        id: u64,
+    },
+}
+
+#[derive(Default)]
+pub(crate) struct HoverTracker {
+    /// Hovered ids of the previous frame, with local mouse position
+    prev: Vec<(u64, Vec2)>,
+    /// Hovered ids of this frame so far
+    current: Vec<(u64, Vec2)>,
+    events: Vec<HoverEvent>,
+}
+
+impl HoverTracker {
+    /// Finish the frame: anything hovered last frame but not this one was left
+    pub(crate) fn end_frame(&mut self) {
+        self.events.clear();
+        for &(id, _) in &self.prev {
+            if !self.current.iter().any(|&(cid, _)| cid == id) {
+                self.events.push(HoverEvent::Leave { id });
+            }
+        }
+        self.prev = std::mem::take(&mut self.current);
+    }
+}
+
+impl RenderArea2D {
+    /// Track whether the mouse hovers `rect` (in world coordinates) this frame.
+    ///
+    /// Call this every frame for every rect you're interested in. Enter and move events
+    /// show up in [`Self::hover_events`] right away, leave events after the next
+    /// [`Self::update`]. A rect that stops being tracked counts as left.
+    pub fn track_hover(&mut self, id: u64, rect: Rect) {
+        let mouse = self.mouse_pos_cam();
+        if !self.focused || !rect.contains(mouse) {
+            return;
+        }
+        let local = mouse - rect.point();
+        let hover = &mut self.hover;
+        match hover.prev.iter().find(|&&(pid, _)| pid == id) {
+            None => hover.events.push(HoverEvent::Enter { id, local }),
+            Some(&(_, prev_local)) if prev_local != local => {
+                hover.events.push(HoverEvent::Move { id, local });
+            }
+            Some(_) => {}
+        }
+        hover.current.push((id, local));
+    }
+    /// Hover events for the rects tracked with [`Self::track_hover`] this frame
+    pub fn hover_events(&self) -> impl Iterator<Item = &HoverEvent> {
+        self.hover.events.iter()
+    }
+}
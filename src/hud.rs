@@ -0,0 +1,59 @@
+//! Drawing crisp UI at window resolution over the presented area.
+
+use crate::{Anchor, RenderArea2D};
+use macroquad::prelude::*;
+
+/// Coordinate helpers passed to [`RenderArea2D::draw_hud`]
+#[derive(Clone, Copy)]
+pub struct HudCtx<'a> {
+    area: &'a RenderArea2D,
+}
+
+impl HudCtx<'_> {
+    /// The rectangle the area is presented in, in window pixels
+    pub fn rect(&self) -> Rect {
+        self.area.hud_rect()
+    }
+    /// Window pixels per virtual pixel, for sizing things like the art around them
+    pub fn scale(&self) -> Vec2 {
+        self.area.presented_scale()
+    }
+    /// Window position of a position in virtual pixels of the area
+    pub fn area_to_window(&self, area: impl Into<Vec2>) -> Vec2 {
+        self.area.area_to_screen(area)
+    }
+    /// Window position of a world position, for anchoring labels to things in the game
+    pub fn world_to_window(&self, world: impl Into<Vec2>) -> Vec2 {
+        self.area.world_to_screen(world)
+    }
+}
+
+impl RenderArea2D {
+    /// The rectangle the area is presented in, in window pixels, for laying out a HUD
+    pub fn hud_rect(&self) -> Rect {
+        self.viewport_rect()
+    }
+    /// Draw a HUD at native window resolution with `draw`, so text stays sharp instead
+    /// of being scaled up with the pixel art.
+    ///
+    /// Call this after [`Self::draw`]. `draw` runs with the default camera, and the
+    /// previously active camera is restored afterwards.
+    pub fn draw_hud(&self, draw: impl FnOnce(HudCtx)) {
+        push_camera_state();
+        set_default_camera();
+        draw(HudCtx { area: self });
+        pop_camera_state();
+    }
+    /// Draw `text` at native window resolution, placed at `anchor` of [`Self::hud_rect`].
+    ///
+    /// The text is sized in window pixels by `params` and isn't scaled with the area,
+    /// so subtitles stay crisp over pixel art. Call this after [`Self::draw`].
+    /// Multiple lines aren't supported, split them yourself.
+    pub fn draw_screen_text(&self, text: &str, anchor: Anchor, params: TextParams) {
+        let dims = measure_text(text, params.font, params.font_size, params.font_scale);
+        let pos = anchor.place(self.hud_rect(), vec2(dims.width, dims.height));
+        self.draw_hud(|_| {
+            draw_text_ex(text, pos.x, pos.y + dims.offset_y, params);
+        });
+    }
+}
@@ -0,0 +1,45 @@
+//! Mapping positions from arbitrary input devices into the render area.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// Maps window positions into the render area.
+///
+/// The built-in mouse support goes through the same mapping, so positions from pen
+/// tablets, custom touch backends or network-forwarded input line up with it exactly.
+pub trait InputTransform {
+    /// Convert a window position into virtual pixels, rounded like the mouse position
+    fn window_to_virtual(&self, window: Vec2) -> Vec2;
+    /// Convert a window position into world coordinates, like the camera-relative mouse position
+    fn window_to_world(&self, window: Vec2) -> Vec2;
+}
+
+impl InputTransform for RenderArea2D {
+    fn window_to_virtual(&self, window: Vec2) -> Vec2 {
+        self.screen_to_pixel(window)
+    }
+    fn window_to_world(&self, window: Vec2) -> Vec2 {
+        self.area_to_world(self.screen_to_pixel(window))
+    }
+}
+
+impl RenderArea2D {
+    /// macroquad's current touches, with positions in virtual pixels like [`Self::mouse_pos`]
+    pub fn touches(&self) -> Vec<Touch> {
+        self.map_touches(|p| self.window_to_virtual(p))
+    }
+    /// macroquad's current touches, with positions in world coordinates like
+    /// [`Self::mouse_pos_cam`]
+    pub fn touches_cam(&self) -> Vec<Touch> {
+        self.map_touches(|p| self.window_to_world(p))
+    }
+    fn map_touches(&self, map: impl Fn(Vec2) -> Vec2) -> Vec<Touch> {
+        touches()
+            .into_iter()
+            .map(|touch| Touch {
+                position: map(touch.position),
+                ..touch
+            })
+            .collect()
+    }
+}
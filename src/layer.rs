@@ -0,0 +1,192 @@
+//! Stacks of render areas composited in order.
+
+use crate::{composite, pixel_camera, PassAlpha, RenderArea2D};
+use macroquad::prelude::*;
+
+/// How a layer is blended over the ones below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayerBlend {
+    /// Regular alpha blending
+    #[default]
+    Alpha,
+    /// Add the layer's color on top, good for glows and light layers
+    Additive,
+}
+
+/// Which layers [`LayerStack::capture`] composites
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOptions {
+    /// Names of the layers to include, whether visible or not.
+    /// `None` includes the visible layers, like [`LayerStack::draw_all`].
+    ///
+    /// Leave out the HUD for marketing shots, or include only the HUD for UI review.
+    pub include_layers: Option<Vec<String>>,
+}
+
+struct Layer {
+    name: String,
+    area: RenderArea2D,
+    opacity: f32,
+    blend: LayerBlend,
+    visible: bool,
+    shake_exempt: bool,
+}
+
+/// Ways to refer to a layer of a [`LayerStack`]: its index, or its name
+pub trait LayerKey {
+    /// Index of the layer this refers to, if there is one
+    fn index_in(&self, stack: &LayerStack) -> Option<usize>;
+}
+
+impl LayerKey for usize {
+    fn index_in(&self, stack: &LayerStack) -> Option<usize> {
+        (*self < stack.layers.len()).then_some(*self)
+    }
+}
+
+impl LayerKey for &str {
+    fn index_in(&self, stack: &LayerStack) -> Option<usize> {
+        stack.layers.iter().position(|l| l.name == *self)
+    }
+}
+
+/// Several render areas, each with its own virtual resolution and camera,
+/// presented on top of each other in order.
+///
+/// This lets a parallax background, a pixel-art world and a higher resolution UI coexist.
+#[derive(Default)]
+pub struct LayerStack {
+    layers: Vec<Layer>,
+}
+
+impl LayerStack {
+    /// Create an empty stack
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add `area` on top of all layers so far, and return its index
+    pub fn add_layer(&mut self, name: impl Into<String>, area: RenderArea2D) -> usize {
+        self.layers.push(Layer {
+            name: name.into(),
+            area,
+            opacity: 1.0,
+            blend: LayerBlend::Alpha,
+            visible: true,
+            shake_exempt: false,
+        });
+        self.layers.len() - 1
+    }
+    /// Number of layers
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+    /// Whether there are no layers
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+    /// Estimated GPU memory of all layers, see [`RenderArea2D::gpu_memory_estimate`]
+    pub fn gpu_memory_estimate(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|layer| layer.area.gpu_memory_estimate())
+            .sum()
+    }
+    fn get_mut(&mut self, key: impl LayerKey) -> Option<&mut Layer> {
+        let index = key.index_in(self)?;
+        self.layers.get_mut(index)
+    }
+    /// The render area of a layer
+    pub fn layer(&self, key: impl LayerKey) -> Option<&RenderArea2D> {
+        let index = key.index_in(self)?;
+        self.layers.get(index).map(|l| &l.area)
+    }
+    /// The render area of a layer, for drawing into it or moving its camera
+    pub fn layer_mut(&mut self, key: impl LayerKey) -> Option<&mut RenderArea2D> {
+        self.get_mut(key).map(|l| &mut l.area)
+    }
+    /// Set the opacity of a layer, from 0 to 1. Returns whether the layer exists.
+    pub fn set_opacity(&mut self, key: impl LayerKey, opacity: f32) -> bool {
+        self.get_mut(key).map(|l| l.opacity = opacity).is_some()
+    }
+    /// Set how a layer is blended. Returns whether the layer exists.
+    pub fn set_blend(&mut self, key: impl LayerKey, blend: LayerBlend) -> bool {
+        self.get_mut(key).map(|l| l.blend = blend).is_some()
+    }
+    /// Show or hide a layer. Returns whether the layer exists.
+    pub fn set_visible(&mut self, key: impl LayerKey, visible: bool) -> bool {
+        self.get_mut(key).map(|l| l.visible = visible).is_some()
+    }
+    /// Keep a layer still when the stack is shaken, like a HUD.
+    /// Returns whether the layer exists.
+    pub fn set_shake_exempt(&mut self, key: impl LayerKey, exempt: bool) -> bool {
+        self.get_mut(key).map(|l| l.shake_exempt = exempt).is_some()
+    }
+    /// [`RenderArea2D::shake`] all layers that aren't shake exempt
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        for layer in self.layers.iter_mut().filter(|l| !l.shake_exempt) {
+            layer.area.shake(intensity, duration);
+        }
+    }
+    /// [`RenderArea2D::update`] all layers
+    pub fn update(&mut self, dt: f32) {
+        for layer in &mut self.layers {
+            layer.area.update(dt);
+        }
+    }
+    /// Composite the layers chosen by `opts` into an image, bottom first.
+    ///
+    /// The image has the largest virtual resolution among the included layers, and
+    /// smaller layers are stretched to it. Like [`RenderArea2D::capture`], this reads
+    /// back from the GPU.
+    pub fn capture(&self, opts: &CaptureOptions) -> Image {
+        let included: Vec<&Layer> = self
+            .layers
+            .iter()
+            .filter(|l| match &opts.include_layers {
+                Some(names) => names.contains(&l.name),
+                None => l.visible,
+            })
+            .filter(|l| !l.area.suspended)
+            .collect();
+        let (width, height) = included
+            .iter()
+            .map(|l| l.area.virtual_size())
+            .fold((1, 1), |(w, h), (lw, lh)| (w.max(lw), h.max(lh)));
+        let rt = render_target(width.into(), height.into());
+        rt.texture.set_filter(FilterMode::Nearest);
+        push_camera_state();
+        set_camera(&pixel_camera(&rt, width, height));
+        clear_background(BLANK);
+        for layer in included {
+            let area = &layer.area;
+            composite::blit(
+                &area.render_target.texture,
+                Vec2::ZERO,
+                DrawTextureParams {
+                    dest_size: Some(vec2(f32::from(width), f32::from(height))),
+                    source: Some(area.pixel_bounds()),
+                    ..Default::default()
+                },
+                &composite::BlitOptions {
+                    alpha: PassAlpha::Straight,
+                    mask: None,
+                    color_space: area.color_space,
+                    tint: Color::new(1.0, 1.0, 1.0, layer.opacity),
+                    blend: layer.blend,
+                },
+                &area.blit_materials,
+            );
+        }
+        pop_camera_state();
+        rt.texture.get_texture_data()
+    }
+    /// Draw all visible layers to the window, bottom first.
+    ///
+    /// You need to first set the default camera with macroquad's `set_default_camera()`.
+    pub fn draw_all(&self) {
+        for layer in self.layers.iter().filter(|l| l.visible) {
+            let tint = Color::new(1.0, 1.0, 1.0, layer.opacity);
+            layer.area.draw_blended(tint, layer.blend);
+        }
+    }
+}
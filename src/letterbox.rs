@@ -0,0 +1,90 @@
+//! Filling the parts of the window the render area doesn't cover.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+use std::rc::Rc;
+
+/// What to fill the bars around the render area with, see [`RenderArea2D::set_letterbox`]
+#[derive(Clone, Default)]
+pub enum LetterboxStyle {
+    /// Leave the bars alone, showing whatever was drawn there before
+    #[default]
+    None,
+    /// Fill with a color
+    Color(Color),
+    /// Tile a texture, aligned to the top-left of the window
+    Tiled(Texture2D),
+    /// Call a function with each bar rectangle, in window coordinates
+    Custom(Rc<dyn Fn(Rect)>),
+}
+
+impl LetterboxStyle {
+    fn draw_bar(&self, bar: Rect) {
+        match self {
+            Self::None => {}
+            Self::Color(color) => draw_rectangle(bar.x, bar.y, bar.w, bar.h, *color),
+            Self::Tiled(tex) => draw_tiled(tex, bar),
+            Self::Custom(f) => f(bar),
+        }
+    }
+}
+
+fn draw_tiled(tex: &Texture2D, bar: Rect) {
+    let tile = tex.size();
+    if tile.x <= 0.0 || tile.y <= 0.0 {
+        return;
+    }
+    let first = (bar.point() / tile).floor() * tile;
+    let mut y = first.y;
+    while y < bar.bottom() {
+        let mut x = first.x;
+        while x < bar.right() {
+            if let Some(part) = Rect::new(x, y, tile.x, tile.y).intersect(bar) {
+                let params = DrawTextureParams {
+                    dest_size: Some(part.size()),
+                    source: Some(Rect::new(part.x - x, part.y - y, part.w, part.h)),
+                    ..Default::default()
+                };
+                draw_texture_ex(tex, part.x, part.y, WHITE, params);
+            }
+            x += tile.x;
+        }
+        y += tile.y;
+    }
+}
+
+impl RenderArea2D {
+    /// Set how [`Self::draw`] fills the space around the render area
+    pub fn set_letterbox(&mut self, style: LetterboxStyle) {
+        self.letterbox = style;
+    }
+    /// The bars between the render area and the edges of the space it's fitted in
+    fn letterbox_bars(&self) -> [Rect; 4] {
+        let avail = self.available_rect();
+        let area = self
+            .viewport_rect()
+            .intersect(avail)
+            .unwrap_or(Rect::new(avail.x, avail.y, 0.0, 0.0));
+        [
+            Rect::new(avail.x, avail.y, avail.w, area.y - avail.y),
+            Rect::new(
+                avail.x,
+                area.bottom(),
+                avail.w,
+                avail.bottom() - area.bottom(),
+            ),
+            Rect::new(avail.x, area.y, area.x - avail.x, area.h),
+            Rect::new(area.right(), area.y, avail.right() - area.right(), area.h),
+        ]
+    }
+    pub(crate) fn draw_letterbox(&self) {
+        if matches!(self.letterbox, LetterboxStyle::None) {
+            return;
+        }
+        for bar in self.letterbox_bars() {
+            if bar.w > 0.0 && bar.h > 0.0 {
+                self.letterbox.draw_bar(bar);
+            }
+        }
+    }
+}
@@ -5,7 +5,94 @@
 
 #![warn(missing_docs)]
 
+use macroquad::miniquad::CursorIcon;
 use macroquad::prelude::*;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::HashMap;
+
+mod background;
+mod blur;
+mod builder;
+mod cache;
+mod camera_state;
+#[cfg(all(feature = "png", not(target_arch = "wasm32")))]
+mod capture;
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+mod clipboard;
+mod composite;
+mod debug_step;
+mod diagnostics;
+mod diff;
+mod draw_queue;
+mod effect;
+mod follow;
+mod font;
+mod guard;
+mod hover;
+mod hud;
+mod input;
+mod layer;
+mod letterbox;
+mod memory;
+mod orientation;
+mod pan_zoom;
+mod passthrough;
+#[cfg(feature = "picking")]
+mod picking;
+mod presentation;
+mod pulse;
+mod quality;
+mod resize;
+mod scroll_blit;
+mod shader;
+mod shake;
+mod snap;
+mod splash;
+mod split;
+mod subtitle;
+mod tile;
+#[cfg(all(feature = "timelapse", not(target_arch = "wasm32")))]
+mod timelapse;
+mod timing;
+mod toast;
+mod transition;
+mod view;
+#[cfg(feature = "weather")]
+mod weather;
+mod window_focus;
+
+pub use background::{Background, Starfield};
+pub use builder::RenderArea2DBuilder;
+pub use camera_state::{CameraState, Easing};
+pub use composite::ColorSpace;
+#[cfg(feature = "log")]
+pub use diagnostics::LogSink;
+pub use diagnostics::{DiagnosticsSink, RenderAreaEvent};
+pub use diff::{DirtyRegion, FrameDiff};
+pub use effect::{post_uniforms, EffectPass, PassAlpha, PassCtx};
+pub use follow::CameraFollow;
+pub use font::{draw_pixel_text, measure_pixel_text};
+pub use guard::AreaGuard;
+pub use hover::HoverEvent;
+pub use hud::HudCtx;
+pub use input::InputTransform;
+pub use layer::{CaptureOptions, LayerBlend, LayerKey, LayerStack};
+pub use letterbox::LetterboxStyle;
+pub use orientation::{Orientation, RotateHint};
+pub use pan_zoom::PanZoomController;
+pub use presentation::{Anchor, EdgePolicy, PresentationTransform, ScaleMode, ScalePolicy};
+pub use pulse::EffectKind;
+pub use quality::{QualityPreset, QualitySettings};
+pub use resize::TargetPadding;
+pub use shake::ShakeWeights;
+pub use splash::Splash;
+pub use split::{SplitLayout, SplitScreen};
+pub use subtitle::SubtitleStyle;
+pub use tile::TileSpace;
+pub use timing::TimedScope;
+pub use transition::Wipe;
+#[cfg(feature = "weather")]
+pub use weather::{Weather, WeatherKind};
 
 /// Acts as a regular screen with the specified dimensions when drawing to it,
 /// but has functionality to be easily rescaled and centered.
@@ -49,103 +136,1466 @@ pub struct RenderArea2D {
     width: u16,
     height: u16,
     scale: u8,
+    scale_mode: ScaleMode,
     camera: Camera2D,
+    focused: bool,
+    overlays: RefCell<Vec<Box<dyn FnOnce()>>>,
+    last_frame: Option<RenderTarget>,
+    frame_blend: Option<f32>,
+    #[cfg(feature = "weather")]
+    weather: Option<weather::WeatherState>,
+    pulses: pulse::Pulses,
+    orientation: Orientation,
+    rotate_hint: RotateHint,
+    input_prediction: bool,
+    last_mouse: Option<Vec2>,
+    mouse_delta: Vec2,
+    temporal_aa: bool,
+    frame_count: Cell<u32>,
+    effects: Option<RefCell<effect::Chain>>,
+    effects_enabled: bool,
+    calibration: Option<quality::Calibration>,
+    calibrated_preset: Option<QualityPreset>,
+    timings: timing::Timings,
+    auto_sanitize: bool,
+    /// Last known good camera target, zoom and rotation
+    last_valid_camera: (Vec2, Vec2, f32),
+    diagnostics: Option<Box<dyn DiagnosticsSink>>,
+    /// Reserved window space as (left, top, right, bottom)
+    margins: (f32, f32, f32, f32),
+    region_cursors: Vec<(Rect, CursorIcon)>,
+    current_cursor: CursorIcon,
+    hover: hover::HoverTracker,
+    #[cfg(all(feature = "timelapse", not(target_arch = "wasm32")))]
+    timelapse: Option<timelapse::Timelapse>,
+    blit_materials: OnceCell<composite::BlitMaterials>,
+    mask: Option<Texture2D>,
+    mask_target: Option<RenderTarget>,
+    color_space: ColorSpace,
+    window_focus: window_focus::WindowFocus,
+    watermark: Option<(Texture2D, Anchor, f32)>,
+    camera_follow: CameraFollow,
+    debug_step: Option<debug_step::DebugStep>,
+    camera_bounds: Option<Rect>,
+    edge_policy: EdgePolicy,
+    shake: shake::Shake,
+    /// Frame contents as of the last `frame_diff`
+    diff_prev: Option<Image>,
+    draw_queue: RefCell<Vec<draw_queue::QueuedSprite>>,
+    /// Window size and DPI scale as of the last `handle_resize`
+    last_screen_size: Option<(Vec2, f32)>,
+    on_resize: Option<resize::ResizeCallback>,
+    letterbox: LetterboxStyle,
+    /// Camera target as of the last draw, for the camera delta of effect passes
+    last_draw_target: Cell<Option<Vec2>>,
+    caches: HashMap<String, cache::CachedLayer>,
+    chunked_caches: HashMap<String, cache::ChunkedLayer>,
+    smooth_pixel_camera: bool,
+    scroll_blit: Option<scroll_blit::ScrollBlit>,
+    flip_y: bool,
+    target_padding: TargetPadding,
+    recreate_on_restore: bool,
+    window_viewport: Option<Rect>,
+    camera_animation: Option<camera_state::CameraAnimation>,
+    base_size: (u16, u16),
+    snap_camera: bool,
+    subtitles: subtitle::Subtitles,
+    /// Set while a [`PanZoomController`] drags the camera, so following pauses
+    manual_pan: bool,
+    toasts: toast::Toasts,
+    clear_color: Option<Color>,
+    /// Frame count when the area was last set for drawing, to clear only once per frame
+    cleared_frame: Cell<Option<u32>>,
+    camera_presets: HashMap<String, CameraState>,
+    transition: Option<transition::Transition>,
+    /// Current supersampling factor, see [`Self::set_supersample`]
+    supersample: u8,
+    /// Supersampling asked for, which dynamic resolution doesn't go above
+    max_supersample: u8,
+    dynamic_resolution: Option<quality::DynamicResolution>,
+    /// Reapplied whenever the render target is recreated
+    filter: FilterMode,
+    /// What gets presented when the previous frame or weather is drawn over the render
+    /// target, so the render target keeps what the game drew
+    composed: RefCell<Option<RenderTarget>>,
+    /// Copies pixels without blending, for reading back part of the render target
+    copy_material: OnceCell<Material>,
+    /// Set by `on_suspend` while the render target is only a placeholder
+    suspended: bool,
+    /// Last presented scale seen, to report [`RenderAreaEvent::ScaleChanged`]
+    reported_scale: Cell<Option<f32>>,
 }
 
 fn target(width: u16, height: u16) -> Vec2 {
     vec2(f32::from(width) / 2.0, f32::from(height) / 2.0)
 }
 
+/// Camera that maps virtual pixel coordinates 1:1 onto `rt`, starting at its top-left
+fn pixel_camera(rt: &RenderTarget, width: u16, height: u16) -> Camera2D {
+    let padded = rt.texture.size() != vec2(f32::from(width), f32::from(height));
+    Camera2D {
+        render_target: Some(rt.clone()),
+        zoom: vec2(2. / f32::from(width), 2. / f32::from(height)),
+        target: target(width, height),
+        viewport: padded.then_some((0, 0, width.into(), height.into())),
+        ..Default::default()
+    }
+}
+
 impl RenderArea2D {
     /// Create a new render area with the specified virtual resolution.
     pub fn new(width: u16, height: u16) -> Self {
         let rt = render_target(width.into(), height.into());
-        let cam = Camera2D {
-            render_target: Some(rt.clone()),
-            zoom: vec2(2. / f32::from(width), 2. / f32::from(height)),
-            target: target(width, height),
-            ..Default::default()
-        };
+        let cam = pixel_camera(&rt, width, height);
         let mut s = Self {
             width,
             height,
             render_target: rt,
             scale: 0,
+            scale_mode: ScaleMode::Integer,
             camera: cam,
+            focused: true,
+            overlays: RefCell::default(),
+            last_frame: None,
+            frame_blend: None,
+            #[cfg(feature = "weather")]
+            weather: None,
+            pulses: pulse::Pulses::default(),
+            orientation: Orientation::Any,
+            rotate_hint: RotateHint::default(),
+            input_prediction: false,
+            last_mouse: None,
+            mouse_delta: Vec2::ZERO,
+            temporal_aa: false,
+            frame_count: Cell::new(0),
+            effects: None,
+            effects_enabled: true,
+            calibration: None,
+            calibrated_preset: None,
+            timings: timing::Timings::default(),
+            auto_sanitize: false,
+            last_valid_camera: (Vec2::ZERO, Vec2::ONE, 0.0),
+            diagnostics: None,
+            margins: (0.0, 0.0, 0.0, 0.0),
+            region_cursors: Vec::new(),
+            current_cursor: CursorIcon::Default,
+            hover: hover::HoverTracker::default(),
+            #[cfg(all(feature = "timelapse", not(target_arch = "wasm32")))]
+            timelapse: None,
+            blit_materials: OnceCell::new(),
+            mask: None,
+            mask_target: None,
+            color_space: ColorSpace::Srgb,
+            window_focus: Default::default(),
+            watermark: None,
+            camera_follow: CameraFollow::default(),
+            debug_step: None,
+            camera_bounds: None,
+            edge_policy: EdgePolicy::Exclusive,
+            shake: shake::Shake::default(),
+            diff_prev: None,
+            draw_queue: RefCell::default(),
+            last_screen_size: None,
+            on_resize: None,
+            letterbox: LetterboxStyle::None,
+            last_draw_target: Cell::new(None),
+            caches: HashMap::new(),
+            chunked_caches: HashMap::new(),
+            smooth_pixel_camera: false,
+            scroll_blit: None,
+            flip_y: false,
+            target_padding: TargetPadding::None,
+            recreate_on_restore: false,
+            window_viewport: None,
+            camera_animation: None,
+            base_size: (width, height),
+            snap_camera: false,
+            subtitles: subtitle::Subtitles::default(),
+            manual_pan: false,
+            toasts: toast::Toasts::default(),
+            clear_color: None,
+            cleared_frame: Cell::new(None),
+            camera_presets: HashMap::new(),
+            transition: None,
+            supersample: 1,
+            max_supersample: 1,
+            dynamic_resolution: None,
+            filter: FilterMode::Nearest,
+            composed: RefCell::default(),
+            copy_material: OnceCell::new(),
+            suspended: false,
+            reported_scale: Cell::new(None),
         };
+        s.last_valid_camera = (s.camera.target, s.camera.zoom, s.camera.rotation);
         s.render_target.texture.set_filter(FilterMode::Nearest);
         s.set_scale_auto();
         s
     }
+    /// Create a render area for viewing a level that is `level_px_size` pixels big.
+    ///
+    /// The virtual resolution is the level size, capped at `max_virtual`.
+    /// The camera starts out showing the top-left corner of the level,
+    /// and is bounded to the level.
+    pub fn fit_level(level_px_size: (u32, u32), max_virtual: (u16, u16)) -> Self {
+        let fit = |level: u32, max: u16| u16::try_from(level).map_or(max, |l| l.min(max));
+        let mut ra = Self::new(
+            fit(level_px_size.0, max_virtual.0),
+            fit(level_px_size.1, max_virtual.1),
+        );
+        ra.set_camera_bounds(Some(Rect::new(
+            0.0,
+            0.0,
+            level_px_size.0 as f32,
+            level_px_size.1 as f32,
+        )));
+        ra
+    }
     /// Sets this render area for drawing.
     ///
     /// Call this before drawing into the render area.
+    ///
+    /// By default the render area is not cleared, so anything drawn into it accumulates
+    /// across frames until it is drawn over or [`Self::clear`] is called.
+    /// With [`Self::set_clear_color`], the first call after each [`Self::draw`] clears it.
     pub fn set(&self) {
+        if self.suspended {
+            // Still draw into the placeholder, not whatever camera was set before
+            set_camera(&self.camera);
+            return;
+        }
+        self.clear_once();
+        if self.draw_offset() == Vec2::ZERO
+            && self.shake.roll() == 0.0
+            && !self.smooth_pixel_camera
+            && !self.snap_camera
+        {
+            set_camera(&self.camera);
+        } else {
+            set_camera(&self.camera_into(&self.render_target));
+        }
+    }
+    /// Clear with the clear color if the area wasn't set yet since the last draw.
+    ///
+    /// Every method that sets a camera for drawing into the area calls this first.
+    fn clear_once(&self) {
+        let frame = self.frame_count.get();
+        if self.cleared_frame.replace(Some(frame)) == Some(frame) {
+            return;
+        }
+        if let Some(color) = self.clear_color {
+            self.clear(color);
+        }
+    }
+    /// Clear the area with `color` automatically at the start of every frame, the first
+    /// time it's set for drawing after each [`Self::draw`], by [`Self::set`],
+    /// [`Self::begin`], [`Self::set_with_parallax`] or [`Self::begin_mask`].
+    ///
+    /// `None`, the default, never clears, for deliberate trails and feedback effects.
+    /// `color` is sRGB, like with [`Self::clear`]. This doesn't combine with
+    /// [`Self::scroll_redraw`], which skips the clear for its frame to keep the
+    /// contents it reuses.
+    pub fn set_clear_color(&mut self, color: Option<Color>) {
+        self.clear_color = color;
+    }
+    /// The color set with [`Self::set_clear_color`]
+    pub fn clear_color(&self) -> Option<Color> {
+        self.clear_color
+    }
+    /// Render into a target one pixel bigger each way, with the camera snapped to whole
+    /// pixels, and shift the final blit by the sub-pixel remainder.
+    ///
+    /// This makes slow camera movement smooth with nearest filtering, without the pixel
+    /// grid of the art swimming. Effect passes then work on the bigger target.
+    /// The contents of the area are lost when toggling this.
+    pub fn set_smooth_pixel_camera(&mut self, enabled: bool) {
+        if self.smooth_pixel_camera != enabled {
+            self.smooth_pixel_camera = enabled;
+            self.recreate_render_target();
+        }
+    }
+    /// The snapped camera for the smooth pixel camera, and the remainder in virtual pixels
+    fn smooth_camera(&self) -> (Camera2D, Vec2) {
+        let zoom = self.zoom();
+        let target = (self.camera.target + self.draw_offset()) * zoom;
+        let snapped = target.floor();
+        let (w, h) = self.target_size();
+        let camera = Camera2D {
+            target: (snapped + Vec2::splat(0.5)) / zoom,
+            zoom: self.camera_zoom(w, h, zoom),
+            rotation: self.camera.rotation + self.shake.roll(),
+            render_target: Some(self.render_target.clone()),
+            viewport: self.camera.viewport,
+            ..Default::default()
+        };
+        (camera, target - snapped)
+    }
+    /// Like [`Self::set`], but with the camera target multiplied by `factor`, for parallax.
+    ///
+    /// A factor of 1 scrolls with the world, 0.5 at half speed, and 0 not at all.
+    /// Zoom, rotation and shake are the same as for the world, so parallax layers
+    /// stay in sync with it.
+    pub fn set_with_parallax(&self, factor: impl Into<Vec2>) {
+        self.clear_once();
+        set_camera(&Camera2D {
+            target: self.snapped_target(self.camera.target * factor.into()) + self.draw_offset(),
+            ..self.camera_into(&self.render_target)
+        });
+    }
+    /// Offset applied to the camera when drawing: shake, and the temporal AA jitter,
+    /// a sub-pixel amount that changes every frame. The jitter is given in virtual
+    /// pixels, so it's converted to world units to stay sub-pixel at any zoom.
+    fn draw_offset(&self) -> Vec2 {
+        const JITTER: [Vec2; 4] = [
+            vec2(0.25, -0.25),
+            vec2(-0.25, 0.25),
+            vec2(-0.25, -0.25),
+            vec2(0.25, 0.25),
+        ];
+        let jitter = if self.temporal_aa {
+            JITTER[self.frame_count.get() as usize % JITTER.len()]
+        } else {
+            Vec2::ZERO
+        };
+        self.shake.offset() + jitter / self.zoom()
+    }
+    /// A copy of the drawing camera that draws into `rt` instead
+    fn camera_into(&self, rt: &RenderTarget) -> Camera2D {
+        if self.smooth_pixel_camera {
+            return Camera2D {
+                render_target: Some(rt.clone()),
+                ..self.smooth_camera().0
+            };
+        }
+        Camera2D {
+            target: self.snapped_target(self.camera.target) + self.draw_offset(),
+            zoom: self.camera.zoom,
+            offset: self.camera.offset,
+            rotation: self.camera.rotation + self.shake.roll(),
+            render_target: Some(rt.clone()),
+            viewport: self.camera.viewport,
+        }
+    }
+    /// Clear the whole render area with `color`.
+    ///
+    /// This can be called at any time, the active camera is left unchanged.
+    /// `color` is sRGB, and is converted to the area's [`ColorSpace`].
+    pub fn clear(&self, color: Color) {
+        push_camera_state();
         set_camera(&self.camera);
+        clear_background(self.color_space.to_internal(color));
+        pop_camera_state();
     }
     /// Set the scale to an integer amount. 2 is 2x zoom for example.
+    ///
+    /// This is the scale used with [`ScaleMode::Integer`], the default mode.
     pub fn set_scale(&mut self, amount: u8) {
         self.scale = amount;
+        self.report_scale_change();
     }
     /// Set the scale automatically to fit the window size.
     pub fn set_scale_auto(&mut self) {
-        self.scale = self.auto_scale();
+        self.fit_virtual_size();
+        self.set_scale(self.auto_scale());
+    }
+    /// Set the filter used when scaling the render area to the window.
+    ///
+    /// The default is [`FilterMode::Nearest`]. The filter is kept when the render
+    /// target is recreated.
+    pub fn set_filter(&mut self, filter: FilterMode) {
+        self.filter = filter;
+        self.render_target.texture.set_filter(filter);
+    }
+    /// The filter set by [`Self::set_filter`]
+    pub fn filter(&self) -> FilterMode {
+        self.filter
+    }
+    /// Apply all the settings of a quality preset at once
+    pub fn apply_preset(&mut self, preset: QualityPreset) {
+        let settings = preset.settings();
+        self.set_supersample(settings.supersample);
+        self.set_dynamic_resolution(settings.dynamic_resolution);
+        self.set_filter(settings.filter);
+        self.set_effects_enabled(settings.effects);
+        self.set_temporal_aa(settings.temporal_aa);
+    }
+    /// Start picking the best quality preset that holds `target_fps`.
+    ///
+    /// Over the next `duration` seconds (as advanced by [`Self::update`]), the presets
+    /// are measured from best to worst, while you render a representative scene.
+    /// The first one that is fast enough gets applied, and is then available from
+    /// [`Self::calibrated_preset`]. If none are, [`QualityPreset::Low`] is picked.
+    pub fn auto_select_preset(&mut self, duration: f32, target_fps: f32) {
+        let cal = quality::Calibration::new(duration, target_fps);
+        self.apply_preset(cal.current());
+        self.calibration = Some(cal);
+        self.calibrated_preset = None;
+    }
+    /// The preset picked by the last [`Self::auto_select_preset`], once it's done
+    pub fn calibrated_preset(&self) -> Option<QualityPreset> {
+        self.calibrated_preset
     }
     /// Get the biggest scale that still fits on the screen
     pub fn auto_scale(&self) -> u8 {
-        let hor_ratio = screen_width() / f32::from(self.width);
-        let ver_ratio = screen_height() / f32::from(self.height);
+        let avail = self.available_rect();
+        let hor_ratio = avail.w / f32::from(self.width);
+        let ver_ratio = avail.h / f32::from(self.height);
         (if hor_ratio < ver_ratio {
             hor_ratio
         } else {
             ver_ratio
         }) as u8
     }
-    /// Get the integer scale
+    /// Get the integer scale set with [`Self::set_scale`]
     pub fn scale(&self) -> u8 {
         self.scale
     }
+    /// Set how the render area is scaled to fit the window. See [`ScaleMode`].
+    pub fn set_scale_mode(&mut self, mode: ScaleMode) {
+        self.scale_mode = mode;
+        self.fit_virtual_size();
+        self.report_scale_change();
+    }
+    /// Set the scale mode from a policy, like [`ScalePolicy::PlatformDefault`] to get
+    /// sensible behavior everywhere without cfg flags in game code.
+    ///
+    /// The mode can still be overridden afterwards with [`Self::set_scale_mode`].
+    pub fn set_scale_policy(&mut self, policy: ScalePolicy) {
+        self.set_scale_mode(policy.resolve());
+    }
+    /// The current scale mode
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.scale_mode
+    }
+    /// Window pixels per virtual pixel on each axis, as presented this frame
+    pub fn presented_scale(&self) -> Vec2 {
+        let avail = self.available_rect();
+        let fit = vec2(avail.w, avail.h) / self.size();
+        match self.scale_mode {
+            ScaleMode::Integer => Vec2::splat(f32::from(self.scale)),
+            ScaleMode::IntegerAuto => Vec2::splat(fit.min_element().floor().max(1.0)),
+            ScaleMode::Fractional => Vec2::splat(fit.min_element()),
+            ScaleMode::Stretch => fit,
+            ScaleMode::FitWidth => Vec2::splat(fit.x),
+            ScaleMode::FitHeight => Vec2::splat(fit.y),
+            ScaleMode::Cover => Vec2::splat(fit.max_element()),
+            ScaleMode::Expand => {
+                let base = vec2(f32::from(self.base_size.0), f32::from(self.base_size.1));
+                let supersample = f32::from(self.supersample);
+                Vec2::splat((vec2(avail.w, avail.h) / base).min_element() / supersample)
+            }
+        }
+    }
+    /// Report [`RenderAreaEvent::ScaleChanged`] if the presented scale differs from the
+    /// last one seen
+    fn report_scale_change(&self) {
+        let new = self.presented_scale().min_element();
+        if let Some(old) = self.reported_scale.replace(Some(new)) {
+            if old != new {
+                self.report(RenderAreaEvent::ScaleChanged { old, new });
+            }
+        }
+    }
     /// Draw this render area to the window.
     ///
     /// You need to first set the default camera with macroquad's `set_default_camera()`.
     pub fn draw(&self) {
-        let params = DrawTextureParams {
-            dest_size: Some(vec2(
-                f32::from(self.width) * f32::from(self.scale),
-                f32::from(self.height) * f32::from(self.scale),
-            )),
+        self.draw_blended(WHITE, LayerBlend::Alpha);
+    }
+    /// [`Self::draw`], tinting the presented area with `tint` and blending it with `blend`
+    pub(crate) fn draw_blended(&self, tint: Color, blend: LayerBlend) {
+        if self.suspended {
+            // Overlays queued in the meantime are dropped, not run all at once on resume
+            self.overlays.take();
+            return;
+        }
+        // Catches window resizes, which change the scale in every mode but Integer
+        self.report_scale_change();
+        let mut params = DrawTextureParams {
+            dest_size: Some(self.size() * self.presented_scale()),
+            source: (self.smooth_pixel_camera || self.target_padding != TargetPadding::None).then(
+                || {
+                    let frac = if self.smooth_pixel_camera {
+                        self.smooth_camera().1
+                    } else {
+                        Vec2::ZERO
+                    };
+                    Rect::new(
+                        frac.x,
+                        frac.y,
+                        f32::from(self.width),
+                        f32::from(self.height),
+                    )
+                },
+            ),
             ..Default::default()
         };
-        let (x_off, y_off) = self.screen_offset();
-        draw_texture_ex(&self.render_target.texture, x_off, y_off, WHITE, params);
+        let (mut x_off, mut y_off) = self.screen_offset();
+        let presented_rect = self.viewport_rect();
+        // Part of the texture showing the whole area, before a transition crops it
+        let full_source = params.source.unwrap_or(self.pixel_bounds());
+        if let Some(transition) = &self.transition {
+            let (src, dest) = transition.place_new(full_source, presented_rect);
+            params.source = Some(src);
+            params.dest_size = Some(dest.size());
+            (x_off, y_off) = (dest.x, dest.y);
+        }
+        self.draw_letterbox();
+        let source = match &self.debug_step {
+            Some(step) => step.source(
+                &self.render_target.texture,
+                self.window_focus.effect_time(),
+                self.frame_count.get(),
+            ),
+            None => self.render_target.texture.clone(),
+        };
+        let frame_blend = self.frame_blend.or(self.temporal_aa.then_some(0.5));
+        let history = frame_blend
+            .zip(self.last_frame.as_ref())
+            .map(|(decay, last)| (&last.texture, decay));
+        #[cfg(feature = "weather")]
+        let weather = self.weather.is_some();
+        #[cfg(not(feature = "weather"))]
+        let weather = false;
+        let source = if history.is_some() || weather {
+            self.compose(&source, history)
+        } else {
+            source
+        };
+        let (presented, alpha) = match &self.effects {
+            Some(chain) if self.effects_enabled => {
+                let _timed = self.begin_timed("post");
+                chain
+                    .borrow_mut()
+                    .run(&source, &self.pass_ctx(), |world| self.world_to_uv(world))
+            }
+            _ => (source.clone(), PassAlpha::Straight),
+        };
+        let mut opts = composite::BlitOptions {
+            alpha,
+            mask: self.mask.as_ref().map(|mask| composite::Mask {
+                texture: mask,
+                // Stretched over the whole area, even while a transition crops it
+                dest: full_source,
+                // A drawn mask has the same layout as the render target
+                source: if self.mask_target.is_some() {
+                    full_source
+                } else {
+                    Rect::new(0.0, 0.0, mask.width(), mask.height())
+                },
+            }),
+            color_space: self.color_space,
+            tint,
+            blend,
+        };
+        composite::blit(
+            &presented,
+            vec2(x_off, y_off),
+            params,
+            &opts,
+            &self.blit_materials,
+        );
+        if let Some(transition) = &self.transition {
+            // The old view is a copy of the render target, from before any effects
+            opts.alpha = PassAlpha::Straight;
+            transition.draw_old(presented_rect, &opts, &self.blit_materials);
+        }
+        self.pulses.draw(self.viewport_rect());
+        for overlay in self.overlays.take() {
+            overlay();
+        }
+        self.subtitles.draw(self.hud_rect());
+        self.toasts.draw(
+            self.pixel_bounds(),
+            |p| self.area_to_screen(p),
+            self.presented_scale().min_element(),
+        );
+        if let Some((tex, anchor, opacity)) = &self.watermark {
+            let pos = anchor.place(self.viewport_rect(), tex.size());
+            draw_texture(tex, pos.x, pos.y, Color::new(1.0, 1.0, 1.0, *opacity));
+        }
+        if let Some(dim) = self.window_focus.active_dim() {
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), dim);
+        }
+        if self.is_orientation_mismatched() {
+            self.rotate_hint.draw();
+        }
+        if let Some(last) = &self.last_frame {
+            push_camera_state();
+            set_camera(&pixel_camera(last, self.width, self.height));
+            clear_background(BLANK);
+            draw_texture(&source, 0.0, 0.0, WHITE);
+            pop_camera_state();
+        }
+        self.frame_count.set(self.frame_count.get().wrapping_add(1));
+        self.last_draw_target.set(Some(self.camera.target));
+        self.timings.end_frame();
+    }
+    /// Copy `source`, draw the previous frame over it with the given opacity and then
+    /// the weather, and return the copy
+    fn compose(&self, source: &Texture2D, history: Option<(&Texture2D, f32)>) -> Texture2D {
+        let (w, h) = self.target_size();
+        let (aw, ah) = self.alloc_size();
+        let mut composed = self.composed.borrow_mut();
+        let rt = match &*composed {
+            Some(rt) if rt.texture.size() == vec2(aw.into(), ah.into()) => rt.clone(),
+            _ => {
+                let rt = render_target(aw.into(), ah.into());
+                composed.insert(rt).clone()
+            }
+        };
+        rt.texture.set_filter(self.filter);
+        push_camera_state();
+        set_camera(&pixel_camera(&rt, w, h));
+        clear_background(BLANK);
+        draw_texture(source, 0.0, 0.0, WHITE);
+        if let Some((last, decay)) = history {
+            draw_texture(last, 0.0, 0.0, Color::new(1.0, 1.0, 1.0, decay));
+        }
+        #[cfg(feature = "weather")]
+        if let Some(weather) = &self.weather {
+            weather.draw();
+        }
+        pop_camera_state();
+        rt.texture.clone()
+    }
+    /// Add a post-processing pass to the end of the effect chain.
+    ///
+    /// Passes run in order on every [`Self::draw`], each one getting the result of the
+    /// previous one, and the final result is what gets presented. The render target
+    /// itself is left untouched.
+    ///
+    /// `name` can be used to toggle the pass later with [`Self::set_pass_enabled`].
+    pub fn add_pass(&mut self, name: impl Into<String>, pass: impl EffectPass + 'static) {
+        let (w, h) = self.alloc_size();
+        self.effects
+            .get_or_insert_with(|| RefCell::new(effect::Chain::new(w, h)))
+            .get_mut()
+            .passes
+            .push(effect::Entry {
+                name: name.into(),
+                enabled: true,
+                pass: Box::new(pass),
+                anchors: Vec::new(),
+            });
+    }
+    /// Keep the `uniform` of all passes called `pass` set to the UV of a world position.
+    ///
+    /// `world_pos` is called every frame before the pass runs, and its result is
+    /// converted with [`Self::world_to_uv`]. This keeps shader effects like shockwaves
+    /// glued to moving objects. The uniform must be declared as a `vec2` in the pass's
+    /// [`EffectPass::material`].
+    /// Returns whether there was a pass called `pass`.
+    pub fn anchor_uniform(
+        &mut self,
+        pass: &str,
+        uniform: &str,
+        world_pos: impl Fn() -> Vec2 + Clone + 'static,
+    ) -> bool {
+        let Some(chain) = &mut self.effects else {
+            return false;
+        };
+        let mut found = false;
+        for entry in chain.get_mut().passes.iter_mut().filter(|e| e.name == pass) {
+            entry.anchors.retain(|(name, _)| name != uniform);
+            entry
+                .anchors
+                .push((uniform.to_string(), Box::new(world_pos.clone())));
+            found = true;
+        }
+        found
+    }
+    /// Enable or disable all passes called `name`.
+    ///
+    /// Disabled passes are skipped without any extra copying.
+    /// Returns whether there was a pass called `name`.
+    pub fn set_pass_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let Some(chain) = &mut self.effects else {
+            return false;
+        };
+        let mut found = false;
+        for entry in chain.get_mut().passes.iter_mut().filter(|e| e.name == name) {
+            entry.enabled = enabled;
+            found = true;
+        }
+        found
+    }
+    /// Enable or disable the whole effect chain, without having to rebuild it
+    pub fn set_effects_enabled(&mut self, enabled: bool) {
+        self.effects_enabled = enabled;
+    }
+    /// Remove all post-processing passes
+    pub fn clear_passes(&mut self) {
+        self.effects = None;
+    }
+    fn pass_ctx(&self) -> PassCtx {
+        let (time, frame) = match &self.debug_step {
+            Some(step) => (step.time.get(), step.frame.get()),
+            None => (self.window_focus.effect_time(), self.frame_count.get()),
+        };
+        PassCtx {
+            size: {
+                let (w, h) = self.target_size();
+                vec2(f32::from(w), f32::from(h))
+            },
+            scale: self.presented_scale().min_element(),
+            time: time as f32,
+            frame,
+            camera_target: self.camera.target,
+            camera_delta: self
+                .last_draw_target
+                .get()
+                .map_or(Vec2::ZERO, |last| self.camera.target - last),
+        }
+    }
+    /// Whole virtual pixels the camera scrolled since the last [`Self::draw`].
+    ///
+    /// Positions are snapped down to whole pixels before comparing, like the
+    /// smooth pixel camera does, so sub-pixel movement adds up correctly over frames.
+    /// Zero before the first draw.
+    pub fn camera_scroll_delta_px(&self) -> IVec2 {
+        let snap = |target: Vec2| (target * self.zoom()).floor().as_ivec2();
+        self.last_draw_target
+            .get()
+            .map_or(IVec2::ZERO, |last| snap(self.camera.target) - snap(last))
+    }
+    /// Name shared by all passes added with [`Self::add_post_effect`]
+    const POST_MATERIAL_PASS: &'static str = "post-material";
+    /// Add a material to the end of the effect chain, drawing the area through it.
+    ///
+    /// The uniforms listed in [`post_uniforms`] are set automatically every frame,
+    /// so the material should declare them. All such passes share the name `"post-material"`,
+    /// for [`Self::set_pass_enabled`] and [`Self::anchor_uniform`].
+    pub fn add_post_effect(&mut self, material: Material) {
+        self.add_pass(Self::POST_MATERIAL_PASS, effect::MaterialPass(material));
+    }
+    /// Replace all materials added with [`Self::add_post_effect`] with `material`,
+    /// or just remove them with `None`. Other passes are kept.
+    pub fn set_post_material(&mut self, material: Option<Material>) {
+        if let Some(chain) = &mut self.effects {
+            chain
+                .get_mut()
+                .passes
+                .retain(|e| e.name != Self::POST_MATERIAL_PASS);
+        }
+        if let Some(material) = material {
+            self.add_post_effect(material);
+        }
+    }
+    /// Mask the render area with `mask` when presenting it, or `None` to stop masking.
+    ///
+    /// The alpha channel of the mask decides how opaque each pixel of the area is.
+    /// The mask is stretched over the whole area, so it should usually be the same size.
+    /// This lets one area show through another, like a "dark world" visible only
+    /// around the player.
+    pub fn set_mask_texture(&mut self, mask: Option<Texture2D>) {
+        self.mask = mask;
+        self.mask_target = None;
+    }
+    /// Set how colors are stored in the render target. See [`ColorSpace`].
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
+    /// The color space used inside the render target
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+    /// Start drawing the mask, in the same world coordinates as the area itself.
+    ///
+    /// The mask is cleared to fully transparent, so only what you draw will be visible.
+    /// This replaces any mask set with [`Self::set_mask_texture`]. Call [`Self::set`]
+    /// or `set_default_camera()` when done.
+    pub fn begin_mask(&mut self) {
+        self.clear_once();
+        let (w, h) = self.alloc_size();
+        let rt = self
+            .mask_target
+            .get_or_insert_with(|| render_target(w.into(), h.into()));
+        rt.texture.set_filter(FilterMode::Nearest);
+        self.mask = Some(rt.texture.clone());
+        let rt = rt.clone();
+        set_camera(&self.camera_into(&rt));
+        clear_background(BLANK);
+    }
+    /// The render target texture of this area, e.g. for use as another area's mask
+    pub fn texture(&self) -> &Texture2D {
+        &self.render_target.texture
+    }
+    /// Stamp `texture` over the presented area at `anchor`, with `opacity` from 0 to 1.
+    ///
+    /// The watermark is drawn at its own size in window pixels, over everything
+    /// else including overlays, so demo builds can be marked with one call.
+    pub fn set_watermark(&mut self, texture: Texture2D, anchor: Anchor, opacity: f32) {
+        self.watermark = Some((texture, anchor, opacity));
+    }
+    /// Remove the watermark set with [`Self::set_watermark`]
+    pub fn clear_watermark(&mut self) {
+        self.watermark = None;
+    }
+    /// Enable or disable keeping a copy of the last presented frame.
+    ///
+    /// When enabled, every [`Self::draw`] copies the render target, with the previous
+    /// frame [blended](Self::set_frame_blend) in, into a second texture, available via
+    /// [`Self::last_frame_texture`]. Useful for feedback effects.
+    pub fn set_keep_last_frame(&mut self, keep: bool) {
+        if !keep {
+            self.last_frame = None;
+        } else if self.last_frame.is_none() {
+            let rt = render_target(self.width.into(), self.height.into());
+            rt.texture.set_filter(FilterMode::Nearest);
+            self.last_frame = Some(rt);
+        }
+    }
+    /// Blend the previous frame over the current one on every [`Self::draw`].
+    ///
+    /// `decay` is the opacity of the previous frame, from `0.0` (no trail) to `1.0`
+    /// (frozen image). Since the blended result is what gets kept, trails fade out
+    /// exponentially. `None` turns blending off.
+    /// Blending happens when presenting, so the render target, and with it
+    /// [`Self::capture`], keeps what was drawn into it.
+    ///
+    /// Enabling this also enables [`Self::set_keep_last_frame`].
+    pub fn set_frame_blend(&mut self, decay: Option<f32>) {
+        self.frame_blend = decay.map(|d| d.clamp(0.0, 1.0));
+        if decay.is_some() {
+            self.set_keep_last_frame(true);
+        }
+    }
+    /// Enable or disable temporal anti-aliasing.
+    ///
+    /// The camera is jittered by a quarter pixel every frame, and each frame is blended
+    /// with the previous one, which smooths the edges of rotated or scaled art drawn into
+    /// a low resolution target. If [frame blending](Self::set_frame_blend) is also enabled,
+    /// its decay is used for the blend. Like frame blending, this happens when presenting,
+    /// so [`Self::capture`] and the frame hash see the jittered frame as drawn.
+    ///
+    /// Enabling this also enables [`Self::set_keep_last_frame`].
+    pub fn set_temporal_aa(&mut self, enabled: bool) {
+        self.temporal_aa = enabled;
+        if enabled {
+            self.set_keep_last_frame(true);
+        }
+    }
+    /// The frame that was presented by the last [`Self::draw`], if enabled with
+    /// [`Self::set_keep_last_frame`]
+    pub fn last_frame_texture(&self) -> Option<&Texture2D> {
+        self.last_frame.as_ref().map(|rt| &rt.texture)
+    }
+    /// Queue a function to be run in window space right after the next [`Self::draw`].
+    ///
+    /// Useful for screen-space annotations from code that has no access to the presentation step.
+    pub fn queue_overlay(&self, f: impl FnOnce() + 'static) {
+        self.overlays.borrow_mut().push(Box::new(f));
+    }
+    /// Advance time-based effects by `dt` seconds.
+    ///
+    /// Call this once per frame, e.g. with macroquad's `get_frame_time()`.
+    pub fn update(&mut self, dt: f32) {
+        #[cfg(all(feature = "web", target_arch = "wasm32"))]
+        self.handle_resize();
+        if self.scale_mode == ScaleMode::Expand {
+            // Only recreates the target if the expanded size changed
+            self.fit_virtual_size();
+        }
+        if self.auto_sanitize {
+            self.sanitize_camera();
+        }
+        let frozen = match &mut self.debug_step {
+            Some(step) => !std::mem::take(&mut step.step_update),
+            None => false,
+        };
+        if !frozen {
+            self.pulses.update(dt);
+            self.shake.update(dt);
+            self.update_camera_animation(dt);
+            self.update_transition(dt);
+            self.subtitles.update(dt);
+            self.toasts.update(dt);
+        }
+        let (current, max) = (self.supersample, self.max_supersample);
+        if let Some(next) = self
+            .dynamic_resolution
+            .as_mut()
+            .and_then(|dynamic| dynamic.record(dt, current, max))
+        {
+            self.apply_supersample(next);
+        }
+        if let Some(cal) = &mut self.calibration {
+            let before = cal.current();
+            if let Some(chosen) = cal.record(dt) {
+                self.calibration = None;
+                self.calibrated_preset = Some(chosen);
+                self.apply_preset(chosen);
+            } else if cal.current() != before {
+                let next = cal.current();
+                self.apply_preset(next);
+            }
+        }
+        self.update_cursor();
+        self.hover.end_frame();
+        self.window_focus.update();
+        if self.window_focus.take_restored() && self.recreate_on_restore {
+            self.recreate_gpu_resources();
+        }
+        #[cfg(all(feature = "timelapse", not(target_arch = "wasm32")))]
+        self.update_timelapse(dt);
+        if self.input_prediction {
+            let mouse = Vec2::from(mouse_position());
+            self.mouse_delta = self.last_mouse.map_or(Vec2::ZERO, |last| mouse - last);
+            self.last_mouse = Some(mouse);
+        }
+        #[cfg(feature = "weather")]
+        {
+            let (size, zoom) = (self.size(), self.zoom());
+            if let Some(weather) = &mut self.weather {
+                weather.update(dt, self.camera.target, zoom, size);
+            }
+        }
+    }
+    /// Start a brief screen-space effect, like a red vignette when taking damage.
+    ///
+    /// The effect fades out linearly from `strength` over `duration` seconds, as advanced
+    /// by [`Self::update`]. Effects of the same kind stack. They are drawn over the
+    /// area by [`Self::draw`].
+    pub fn pulse_effect(&mut self, kind: EffectKind, strength: f32, duration: f32) {
+        self.pulses.push(kind, strength, duration);
+    }
+    /// Show `cursor` while the mouse hovers `rect`, given in virtual pixels.
+    ///
+    /// The mouse cursor is updated by [`Self::update`]. Where regions overlap,
+    /// the one registered last wins.
+    pub fn set_region_cursor(&mut self, rect: Rect, cursor: CursorIcon) {
+        self.region_cursors.push((rect, cursor));
+    }
+    /// Remove all regions registered with [`Self::set_region_cursor`]
+    pub fn clear_region_cursors(&mut self) {
+        self.region_cursors.clear();
+    }
+    fn update_cursor(&mut self) {
+        let mouse = self.mouse_pos();
+        let wanted = self
+            .region_cursors
+            .iter()
+            .rev()
+            .find(|(rect, _)| self.focused && rect.contains(mouse))
+            .map_or(CursorIcon::Default, |(_, cursor)| *cursor);
+        if wanted != self.current_cursor {
+            macroquad::miniquad::window::set_mouse_cursor(wanted);
+            self.current_cursor = wanted;
+        }
+    }
+    /// Set the weather overlay, or `None` for clear skies.
+    ///
+    /// The particles are simulated by [`Self::update`] and drawn over the render area by
+    /// [`Self::draw`], right before presenting it. They aren't drawn into the render
+    /// target, so they don't pile up without clearing and aren't part of captures.
+    #[cfg(feature = "weather")]
+    pub fn set_weather(&mut self, weather: Option<Weather>) {
+        match (&mut self.weather, weather) {
+            (Some(state), Some(config)) => state.config = config,
+            (state, config) => *state = config.map(weather::WeatherState::new),
+        }
+    }
+    /// Set the screen orientation the game is designed for.
+    ///
+    /// While the window goes against it, [`Self::draw`] covers the window with the
+    /// [rotate hint](Self::set_rotate_hint).
+    pub fn set_preferred_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+    /// Set what to show when the window doesn't match the preferred orientation
+    pub fn set_rotate_hint(&mut self, hint: RotateHint) {
+        self.rotate_hint = hint;
+    }
+    /// Whether the window currently goes against the preferred orientation
+    pub fn is_orientation_mismatched(&self) -> bool {
+        self.orientation
+            .is_mismatched(vec2(screen_width(), screen_height()))
     }
     /// Gives mouse position translated to the render area coordinates
     pub fn mouse_position(&self) -> (f32, f32) {
-        let (mx, my) = mouse_position();
-        let (x_off, y_off) = self.screen_offset();
-        (
-            ((mx - x_off) / f32::from(self.scale)).floor(),
-            ((my - y_off) / f32::from(self.scale)).floor(),
-        )
+        self.mouse_pos().into()
     }
     /// Gives mouse position translated to the render area coordinates, including camera offset
     pub fn mouse_position_cam(&self) -> (f32, f32) {
-        let (mx, my) = self.mouse_position();
-        let offs = self.camera.target - target(self.width, self.height);
-        (mx + offs.x, my + offs.y)
+        self.mouse_pos_cam().into()
+    }
+    /// Enable or disable mouse position prediction.
+    ///
+    /// When enabled, the mouse position queries extrapolate the cursor one frame ahead,
+    /// based on its movement between the last two [`Self::update`] calls. This hides
+    /// some of the latency of presenting through a render target in fast-paced games.
+    pub fn set_input_prediction(&mut self, enabled: bool) {
+        self.input_prediction = enabled;
+        self.last_mouse = None;
+        self.mouse_delta = Vec2::ZERO;
+    }
+    /// Mouse position in window space, extrapolated if input prediction is enabled
+    fn window_mouse(&self) -> Vec2 {
+        let mouse = Vec2::from(mouse_position());
+        if self.input_prediction {
+            mouse + self.mouse_delta
+        } else {
+            mouse
+        }
+    }
+    /// Like [`Self::mouse_position`], but as a [`Vec2`]
+    pub fn mouse_pos(&self) -> Vec2 {
+        self.window_to_virtual(self.window_mouse())
+    }
+    /// Map a window position to a whole virtual pixel, rounded by the [`EdgePolicy`].
+    ///
+    /// All mouse position queries go through this.
+    pub fn screen_to_pixel(&self, screen: impl Into<Vec2>) -> Vec2 {
+        self.edge_policy
+            .apply(self.screen_to_area(screen), self.size())
+    }
+    /// Set how window positions are rounded to virtual pixels
+    pub fn set_edge_policy(&mut self, policy: EdgePolicy) {
+        self.edge_policy = policy;
+    }
+    /// Like [`Self::mouse_position_cam`], but as a [`Vec2`]
+    pub fn mouse_pos_cam(&self) -> Vec2 {
+        self.window_to_world(self.window_mouse())
+    }
+    /// The virtual pixel under the mouse cursor, or `None` if the cursor is outside the area
+    pub fn mouse_pixel(&self) -> Option<(i32, i32)> {
+        let p = self.mouse_pos();
+        let (x, y) = (p.x as i32, p.y as i32);
+        ((0..i32::from(self.width)).contains(&x) && (0..i32::from(self.height)).contains(&y))
+            .then_some((x, y))
+    }
+    /// The color of the render target pixel under the mouse cursor.
+    ///
+    /// Like [`Self::read_region`], this reads back from the GPU, but only that one pixel.
+    pub fn pixel_color_at_mouse(&self) -> Option<Color> {
+        if self.suspended {
+            return None;
+        }
+        let (x, y) = self.mouse_pixel()?;
+        let img = self.read_region(Rect::new(x as f32, y as f32, 1.0, 1.0));
+        Some(img.get_pixel(0, 0))
+    }
+    /// Read back the whole render area at virtual resolution.
+    ///
+    /// This doesn't depend on the window size or scale, and has no letterbox bars,
+    /// so it's good for screenshots and visual tests. See [`Self::read_region`] about cost.
+    pub fn capture(&self) -> Image {
+        self.read_region(self.pixel_bounds())
+    }
+    /// Read back the pixels of `rect` (in virtual pixels) from the render target.
+    ///
+    /// `rect` is clipped to the bounds of the render area.
+    /// This reads back from the GPU, so it's not cheap, but only the pixels of `rect`
+    /// are transferred. All capture APIs go through here.
+    pub fn read_region(&self, rect: Rect) -> Image {
+        let rect = rect.intersect(self.pixel_bounds()).unwrap_or_default();
+        let (w, h) = (rect.w as u16, rect.h as u16);
+        if w == 0 || h == 0 || self.suspended {
+            return Image::empty();
+        }
+        // Copy the rect into a target of its own, and read back just that
+        let rt = render_target(w.into(), h.into());
+        push_camera_state();
+        set_camera(&pixel_camera(&rt, w, h));
+        clear_background(BLANK);
+        gl_use_material(
+            self.copy_material
+                .get_or_init(|| shader::load(shader::COPY, MaterialParams::default())),
+        );
+        let params = DrawTextureParams {
+            source: Some(Rect::new(
+                rect.x.floor(),
+                rect.y.floor(),
+                w.into(),
+                h.into(),
+            )),
+            ..Default::default()
+        };
+        draw_texture_ex(&self.render_target.texture, 0.0, 0.0, WHITE, params);
+        gl_use_default_material();
+        pop_camera_state();
+        rt.texture.get_texture_data()
+    }
+    /// A hash of the current contents of the render target.
+    ///
+    /// This is 64-bit FNV-1a over the RGBA bytes, so identical pixels give the same
+    /// hash on every platform. Like [`Self::read_region`], this reads back from the GPU.
+    #[cfg(feature = "frame-hash")]
+    pub fn frame_hash(&self) -> u64 {
+        let bytes = if self.suspended {
+            Vec::new()
+        } else {
+            self.render_target.texture.get_texture_data().bytes
+        };
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+    /// Upload `image` into the render target, with its top-left corner at (`x`, `y`) virtual pixels.
+    ///
+    /// Parts of the image that fall outside the render area are discarded.
+    /// The pixels stay until they are drawn over or the render area is cleared.
+    pub fn write_region(&self, x: i32, y: i32, image: &Image) {
+        let dst = Rect::new(
+            x as f32,
+            y as f32,
+            f32::from(image.width),
+            f32::from(image.height),
+        );
+        let Some(dst) = dst.intersect(self.pixel_bounds()) else {
+            return;
+        };
+        if dst.w < 1.0 || dst.h < 1.0 || self.suspended {
+            return;
+        }
+        let src = Rect::new(dst.x - x as f32, dst.y - y as f32, dst.w, dst.h);
+        self.render_target.texture.update_part(
+            &image.sub_image(src),
+            dst.x as i32,
+            dst.y as i32,
+            dst.w as i32,
+            dst.h as i32,
+        );
+    }
+    /// Camera that draws into the render target in virtual pixels, ignoring the camera position
+    fn camera_for_pixels(&self) -> Camera2D {
+        let (w, h) = self.target_size();
+        pixel_camera(&self.render_target, w, h)
+    }
+    /// Camera zoom vector showing `w`x`h` pixels at `zoom`, flipped if world y points up
+    fn camera_zoom(&self, w: u16, h: u16, zoom: f32) -> Vec2 {
+        let flip = if self.flip_y { -1.0 } else { 1.0 };
+        vec2(2.0 / f32::from(w), 2.0 * flip / f32::from(h)) * zoom
+    }
+    /// Size of the render target, which has an extra pixel each way for the smooth camera
+    fn target_size(&self) -> (u16, u16) {
+        let extra = u16::from(self.smooth_pixel_camera);
+        (self.width + extra, self.height + extra)
+    }
+    /// Size the render target is allocated with, after [`TargetPadding`]
+    fn alloc_size(&self) -> (u16, u16) {
+        let (w, h) = self.target_size();
+        self.target_padding.apply(w, h)
+    }
+    /// Viewport restricting drawing to the used part of a padded render target
+    fn target_viewport(&self) -> Option<(i32, i32, i32, i32)> {
+        let (w, h) = self.target_size();
+        (self.alloc_size() != (w, h)).then_some((0, 0, w.into(), h.into()))
+    }
+    /// The render area in virtual pixels, as a rect at the origin
+    fn pixel_bounds(&self) -> Rect {
+        Rect::new(0.0, 0.0, f32::from(self.width), f32::from(self.height))
+    }
+    /// Matrix transforming world coordinates into window coordinates.
+    ///
+    /// This is the camera transform followed by the scaling and centering that
+    /// [`Self::draw`] uses to present the render area.
+    pub fn view_matrix(&self) -> Mat4 {
+        let (x_off, y_off) = self.screen_offset();
+        let blit = Mat4::from_translation(vec3(x_off, y_off, 0.0))
+            * Mat4::from_scale(self.presented_scale().extend(1.0));
+        let clip_to_pixels = Mat4::from_scale(vec3(
+            f32::from(self.width) / 2.0,
+            f32::from(self.height) / 2.0,
+            1.0,
+        )) * Mat4::from_translation(vec3(1.0, 1.0, 0.0));
+        blit * clip_to_pixels * self.camera.matrix()
+    }
+    /// Matrix transforming window coordinates into world coordinates.
+    ///
+    /// The inverse of [`Self::view_matrix`].
+    pub fn inverse_view_matrix(&self) -> Mat4 {
+        self.view_matrix().inverse()
+    }
+    /// Convert a window position, like the mouse cursor, into world coordinates.
+    ///
+    /// This accounts for the camera's position, zoom and rotation, and how the area
+    /// is presented.
+    pub fn screen_to_world(&self, screen: impl Into<Vec2>) -> Vec2 {
+        self.area_to_world(self.screen_to_area(screen))
+    }
+    /// Convert a world position into window coordinates.
+    ///
+    /// The inverse of [`Self::screen_to_world`].
+    pub fn world_to_screen(&self, world: impl Into<Vec2>) -> Vec2 {
+        self.area_to_screen(self.world_to_area(world))
+    }
+    /// Convert a window position into virtual pixels of the render area.
+    ///
+    /// Unlike [`Self::mouse_pos`], the result isn't rounded to whole pixels.
+    pub fn screen_to_area(&self, screen: impl Into<Vec2>) -> Vec2 {
+        self.presentation_transform().window_to_area(screen.into())
+    }
+    /// Convert virtual pixels of the render area into a window position
+    pub fn area_to_screen(&self, area: impl Into<Vec2>) -> Vec2 {
+        self.presentation_transform().area_to_window(area.into())
+    }
+    /// Convert virtual pixels of the render area into world coordinates through the camera
+    pub fn area_to_world(&self, area: impl Into<Vec2>) -> Vec2 {
+        self.uv_to_world(area.into() / self.size())
+    }
+    /// Convert world coordinates into virtual pixels of the render area
+    pub fn world_to_area(&self, world: impl Into<Vec2>) -> Vec2 {
+        self.world_to_uv(world) * self.size()
+    }
+    /// Convert a world position into the render target's UV space.
+    ///
+    /// (0, 0) is the top left of the render area and (1, 1) the bottom right,
+    /// matching `uv` in an [`EffectPass`] shader sampling the area's texture.
+    pub fn world_to_uv(&self, world: impl Into<Vec2>) -> Vec2 {
+        let world = world.into();
+        let clip = self.camera.matrix().project_point3(world.extend(0.0));
+        (clip.truncate() + Vec2::ONE) / 2.0
+    }
+    /// Convert a position in the render target's UV space into world coordinates.
+    ///
+    /// The inverse of [`Self::world_to_uv`].
+    pub fn uv_to_world(&self, uv: impl Into<Vec2>) -> Vec2 {
+        let clip = uv.into() * 2.0 - Vec2::ONE;
+        self.camera
+            .matrix()
+            .inverse()
+            .project_point3(clip.extend(0.0))
+            .truncate()
+    }
+    /// Where and how big [`Self::draw`] presents the render area in the window this frame.
+    ///
+    /// Useful for tracking the area precisely with custom overlays drawn in window space.
+    pub fn presentation_transform(&self) -> PresentationTransform {
+        let (x, y) = self.screen_offset();
+        PresentationTransform {
+            offset: vec2(x, y),
+            scale: self.presented_scale(),
+        }
+    }
+    /// The world-space rectangle currently visible through the camera.
+    ///
+    /// When the camera is rotated, this is the bounding box of the rotated view.
+    pub fn visible_world_rect(&self) -> Rect {
+        let corners = [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE].map(|uv| self.uv_to_world(uv));
+        let min = corners.into_iter().reduce(Vec2::min).unwrap_or_default();
+        let max = corners.into_iter().reduce(Vec2::max).unwrap_or_default();
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+    /// Whether any part of `rect`, in world coordinates, may be visible through the camera.
+    ///
+    /// Cheap enough to call for thousands of tiles or entities per frame.
+    pub fn is_visible(&self, rect: Rect) -> bool {
+        self.visible_world_rect().overlaps(&rect)
+    }
+    /// The rectangle this render area occupies in the window, in window pixels
+    pub fn viewport_rect(&self) -> Rect {
+        let (x, y) = self.screen_offset();
+        let size = self.size() * self.presented_scale();
+        Rect::new(x, y, size.x, size.y)
     }
     fn screen_offset(&self) -> (f32, f32) {
+        let avail = self.available_rect();
+        let size = self.size() * self.presented_scale();
         (
-            (screen_width() - f32::from(self.width) * f32::from(self.scale)) / 2.0,
-            (screen_height() - f32::from(self.height) * f32::from(self.scale)) / 2.0,
+            avail.x + (avail.w - size.x) / 2.0,
+            avail.y + (avail.h - size.y) / 2.0,
+        )
+    }
+    /// The part of the window the render area is fitted and centered in
+    fn available_rect(&self) -> Rect {
+        let (left, top, right, bottom) = self.margins;
+        let outer = self
+            .window_viewport
+            .unwrap_or_else(|| Rect::new(0.0, 0.0, screen_width(), screen_height()));
+        Rect::new(
+            outer.x + left,
+            outer.y + top,
+            (outer.w - left - right).max(0.0),
+            (outer.h - top - bottom).max(0.0),
         )
     }
+    /// Fit the area into `rect` of the window instead of the whole window, or `None`
+    /// for the whole window.
+    ///
+    /// Centering, scaling, letterboxing and mouse mapping all use this rect, so several
+    /// areas can share the window, like for split-screen. See also [`SplitScreen`].
+    pub fn set_viewport(&mut self, rect: Option<Rect>) {
+        self.window_viewport = rect;
+        self.set_scale_auto();
+    }
+    /// The rect set with [`Self::set_viewport`]
+    pub fn viewport(&self) -> Option<Rect> {
+        self.window_viewport
+    }
+    /// Reserve space at the edges of the window, in window pixels.
+    ///
+    /// Auto scaling and centering only consider the rest of the window, so the area
+    /// stays clear of docked UI panels or banners.
+    pub fn reserve_margins(&mut self, left: f32, top: f32, right: f32, bottom: f32) {
+        self.margins = (left, top, right, bottom);
+    }
     /// Move the camera (x, y) by the specified amounts
     pub fn move_camera(&mut self, x: f32, y: f32) {
         self.camera.target += vec2(x, y);
+        self.clamp_camera();
     }
     /// Center the camera on (x, y)
     pub fn center_camera(&mut self, x: f32, y: f32) {
         self.camera.target = vec2(x, y);
+        self.clamp_camera();
+    }
+    /// Keep the camera from showing anything outside `bounds`, or `None` to not limit it.
+    ///
+    /// All camera movement and zooming is clamped from then on. Where the world is
+    /// smaller than the view, the camera is centered on it instead.
+    pub fn set_camera_bounds(&mut self, bounds: Option<Rect>) {
+        self.camera_bounds = bounds;
+        self.clamp_camera();
+    }
+    /// Clamp the camera target to the camera bounds, if there are any
+    pub(crate) fn clamp_camera(&mut self) {
+        let Some(bounds) = self.camera_bounds else {
+            return;
+        };
+        let half = self.visible_world_rect().size() / 2.0;
+        let clamp = |target: f32, min: f32, max: f32, half: f32| {
+            if max - min <= half * 2.0 {
+                (min + max) / 2.0
+            } else {
+                target.clamp(min + half, max - half)
+            }
+        };
+        let t = self.camera.target;
+        self.camera.target = vec2(
+            clamp(t.x, bounds.left(), bounds.right(), half.x),
+            clamp(t.y, bounds.top(), bounds.bottom(), half.y),
+        );
+    }
+    /// Move the camera by `delta`. Accepts a [`Vec2`] or an `(x, y)` tuple.
+    pub fn move_camera_by(&mut self, delta: impl Into<Vec2>) {
+        let d = delta.into();
+        self.move_camera(d.x, d.y);
+    }
+    /// Center the camera on `pos`. Accepts a [`Vec2`] or an `(x, y)` tuple.
+    pub fn center_camera_on(&mut self, pos: impl Into<Vec2>) {
+        let p = pos.into();
+        self.center_camera(p.x, p.y);
+    }
+    /// Set the camera rotation, in degrees
+    pub fn set_rotation(&mut self, degrees: f32) {
+        self.camera.rotation = degrees;
+        self.clamp_camera();
+    }
+    /// Rotate the camera by `delta` degrees
+    pub fn rotate(&mut self, delta: f32) {
+        self.set_rotation(self.camera.rotation + delta);
+    }
+    /// The camera rotation, in degrees
+    pub fn rotation(&self) -> f32 {
+        self.camera.rotation
+    }
+    /// Set the camera magnification. 2.0 shows the world twice as big.
+    ///
+    /// Unlike the scale, this doesn't change how the render area fits the window,
+    /// and the render target keeps its virtual resolution.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.camera.zoom = self.camera_zoom(self.width, self.height, zoom);
+        self.clamp_camera();
+    }
+    /// Make world y point up instead of down, like in most math and physics code.
+    ///
+    /// Only the camera is flipped, so virtual pixels and window positions still go down.
+    pub fn set_flip_y(&mut self, flip: bool) {
+        self.flip_y = flip;
+        self.set_zoom(self.zoom());
+    }
+    /// The camera magnification, see [`Self::set_zoom`]
+    pub fn zoom(&self) -> f32 {
+        self.camera.zoom.x * f32::from(self.width) / 2.0
+    }
+    /// Multiply the zoom by `factor`, keeping `world_point` at the same screen position,
+    /// like for zooming to the center of a selection. See [`Self::zoom_at`].
+    pub fn zoom_around(&mut self, world_point: impl Into<Vec2>, factor: f32) {
+        let p = world_point.into();
+        self.zoom_at(p.x, p.y, factor);
+    }
+    /// Multiply the zoom by `factor`, keeping the world point (`x`, `y`) in place on screen.
+    ///
+    /// Zooming at [`Self::mouse_pos_cam`] gives the usual mouse wheel zoom.
+    pub fn zoom_at(&mut self, x: f32, y: f32, factor: f32) {
+        let anchor = vec2(x, y);
+        self.camera.target = anchor + (self.camera.target - anchor) / factor;
+        self.set_zoom(self.zoom() * factor);
+    }
+    /// Pan the camera with an analog stick, at up to `speed` units per second.
+    ///
+    /// Stick input inside a radial deadzone of 0.2 is ignored, and the rest is rescaled
+    /// and squared for finer control at small deflections. Does nothing if unfocused.
+    pub fn pan_camera_analog(&mut self, stick: Vec2, speed: f32, dt: f32) {
+        const DEADZONE: f32 = 0.2;
+        let len = stick.length();
+        if !self.focused || len <= DEADZONE {
+            return;
+        }
+        let t = ((len.min(1.0) - DEADZONE) / (1.0 - DEADZONE)).powi(2);
+        self.move_camera_by(stick / len * t * speed * dt);
+    }
+    /// Reset the camera to its last valid state if it became NaN or infinite.
+    ///
+    /// A non-finite camera makes everything silently disappear, for example after
+    /// following a bad position. Returns whether the camera had to be reset, which is
+    /// also reported to the [diagnostics sink](Self::set_diagnostics).
+    pub fn sanitize_camera(&mut self) -> bool {
+        let cam = &self.camera;
+        if cam.target.is_finite() && cam.zoom.is_finite() && cam.rotation.is_finite() {
+            self.last_valid_camera = (cam.target, cam.zoom, cam.rotation);
+            return false;
+        }
+        let bad_target = cam.target;
+        (self.camera.target, self.camera.zoom, self.camera.rotation) = self.last_valid_camera;
+        self.report(RenderAreaEvent::CameraSanitized { bad_target });
+        true
+    }
+    /// Call [`Self::sanitize_camera`] on every [`Self::update`]
+    pub fn set_auto_sanitize(&mut self, enabled: bool) {
+        self.auto_sanitize = enabled;
+    }
+    /// Set where to report noteworthy events, like scale changes and camera resets
+    pub fn set_diagnostics(&mut self, sink: impl DiagnosticsSink + 'static) {
+        self.diagnostics = Some(Box::new(sink));
+    }
+    fn report(&self, event: RenderAreaEvent) {
+        if let Some(sink) = &self.diagnostics {
+            sink.event(event);
+        }
+    }
+    /// Set whether this render area has input focus.
+    ///
+    /// Input helpers of an unfocused render area do nothing.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+    /// Whether this render area has input focus
+    pub fn has_focus(&self) -> bool {
+        self.focused
+    }
+    /// The point the camera is centered on
+    pub fn camera_target(&self) -> Vec2 {
+        self.camera.target
+    }
+    /// The virtual resolution of this render area
+    pub fn size(&self) -> Vec2 {
+        vec2(f32::from(self.width), f32::from(self.height))
+    }
+}
+
+/// Give input focus to the render area under the mouse cursor, and take it away from the rest.
+///
+/// Later areas are considered to be drawn on top of earlier ones.
+/// Returns the index of the focused area, if any.
+pub fn focus_under_mouse(areas: &mut [RenderArea2D]) -> Option<usize> {
+    let mouse = Vec2::from(mouse_position());
+    let hit = areas
+        .iter()
+        .rposition(|ra| ra.viewport_rect().contains(mouse));
+    for (i, ra) in areas.iter_mut().enumerate() {
+        ra.focused = Some(i) == hit;
     }
+    hit
 }
@@ -0,0 +1,44 @@
+//! Estimating how much GPU memory a render area uses.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// Bytes of an RGBA8 texture
+pub(crate) fn texture_bytes(texture: &Texture2D) -> usize {
+    texture.width() as usize * texture.height() as usize * 4
+}
+
+impl RenderArea2D {
+    /// Estimated bytes of GPU memory used by the textures this area owns.
+    ///
+    /// This counts the render target, effect pass targets, the mask target, the kept
+    /// last frame, the debug freeze frame and cached layers, all as 4 bytes per pixel.
+    /// Textures you passed in and driver overhead aren't included.
+    pub fn gpu_memory_estimate(&self) -> usize {
+        let targets = [
+            Some(&self.render_target),
+            self.mask_target.as_ref(),
+            self.last_frame.as_ref(),
+        ];
+        targets
+            .into_iter()
+            .flatten()
+            .map(|rt| texture_bytes(&rt.texture))
+            .sum::<usize>()
+            + self.effects.as_ref().map_or(0, |c| c.borrow().gpu_bytes())
+            + self.debug_step.as_ref().map_or(0, |d| d.gpu_bytes())
+            + self.scroll_blit.as_ref().map_or(0, |s| s.gpu_bytes())
+            + self.transition.as_ref().map_or(0, |t| t.gpu_bytes())
+            + self
+                .composed
+                .borrow()
+                .as_ref()
+                .map_or(0, |rt| texture_bytes(&rt.texture))
+            + self.caches.values().map(|c| c.gpu_bytes()).sum::<usize>()
+            + self
+                .chunked_caches
+                .values()
+                .map(|c| c.gpu_bytes())
+                .sum::<usize>()
+    }
+}
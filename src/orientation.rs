@@ -0,0 +1,71 @@
+//! Preferred screen orientation and the "please rotate" overlay.
+
+use macroquad::prelude::*;
+
+/// Screen orientation the game is designed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Any orientation is fine
+    #[default]
+    Any,
+    /// Wider than tall
+    Landscape,
+    /// Taller than wide
+    Portrait,
+}
+
+impl Orientation {
+    /// Whether a window of `size` goes against this orientation
+    pub fn is_mismatched(self, size: Vec2) -> bool {
+        match self {
+            Self::Any => false,
+            Self::Landscape => size.y > size.x,
+            Self::Portrait => size.x > size.y,
+        }
+    }
+}
+
+/// What to show when the window doesn't match the preferred [`Orientation`]
+#[derive(Debug, Clone)]
+pub enum RotateHint {
+    /// Centered text
+    Text(String),
+    /// Centered texture, scaled down to fit if needed
+    Texture(Texture2D),
+}
+
+impl Default for RotateHint {
+    fn default() -> Self {
+        Self::Text("Please rotate your device".into())
+    }
+}
+
+impl RotateHint {
+    /// Draw the hint over the whole window
+    pub(crate) fn draw(&self) {
+        let (sw, sh) = (screen_width(), screen_height());
+        draw_rectangle(0.0, 0.0, sw, sh, BLACK);
+        match self {
+            Self::Text(text) => {
+                let size = (sw.min(sh) / 12.0) as u16;
+                let dims = measure_text(text, None, size, 1.0);
+                draw_text(
+                    text,
+                    (sw - dims.width) / 2.0,
+                    (sh - dims.height) / 2.0 + dims.offset_y,
+                    f32::from(size),
+                    WHITE,
+                );
+            }
+            Self::Texture(tex) => {
+                let fit = (sw / tex.width()).min(sh / tex.height()).min(1.0);
+                let size = tex.size() * fit;
+                let params = DrawTextureParams {
+                    dest_size: Some(size),
+                    ..Default::default()
+                };
+                draw_texture_ex(tex, (sw - size.x) / 2.0, (sh - size.y) / 2.0, WHITE, params);
+            }
+        }
+    }
+}
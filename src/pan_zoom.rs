@@ -0,0 +1,69 @@
+//! Mouse-driven camera panning and zooming, like in level editors.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// Drag-to-pan and wheel zoom toward the cursor, for editors.
+///
+/// Call [`Self::update`] once per frame. Camera bounds still apply, and
+/// [`RenderArea2D::follow`] leaves the camera alone while a drag is going on.
+/// Nothing happens while the area is not [focused](RenderArea2D::set_focused).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanZoomController {
+    /// Mouse button that pans the camera while held
+    pub pan_button: MouseButton,
+    /// How much one wheel step zooms, as a fraction of the current zoom
+    pub zoom_sensitivity: f32,
+    /// Lowest zoom the wheel can reach
+    pub min_zoom: f32,
+    /// Highest zoom the wheel can reach
+    pub max_zoom: f32,
+    /// Window position of the mouse on the last update while dragging
+    drag_from: Option<Vec2>,
+}
+
+impl Default for PanZoomController {
+    fn default() -> Self {
+        Self {
+            pan_button: MouseButton::Middle,
+            zoom_sensitivity: 0.1,
+            min_zoom: 0.25,
+            max_zoom: 8.0,
+            drag_from: None,
+        }
+    }
+}
+
+impl PanZoomController {
+    /// A controller with the default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Whether the camera is being dragged right now
+    pub fn is_dragging(&self) -> bool {
+        self.drag_from.is_some()
+    }
+    /// Pan and zoom `area` from this frame's mouse input
+    pub fn update(&mut self, area: &mut RenderArea2D) {
+        let mouse = Vec2::from(mouse_position());
+        let over = area.viewport_rect().contains(mouse);
+        if !area.has_focus() || !is_mouse_button_down(self.pan_button) {
+            self.drag_from = None;
+        } else if let Some(from) = self.drag_from {
+            let delta = area.screen_to_world(from) - area.screen_to_world(mouse);
+            area.move_camera(delta.x, delta.y);
+            self.drag_from = Some(mouse);
+        } else if over && is_mouse_button_pressed(self.pan_button) {
+            self.drag_from = Some(mouse);
+        }
+        area.manual_pan = self.is_dragging();
+        let wheel = mouse_wheel().1;
+        if area.has_focus() && over && wheel != 0.0 {
+            let zoom = area.zoom();
+            let wanted = (zoom * (1.0 + self.zoom_sensitivity).powf(wheel.signum()))
+                .clamp(self.min_zoom, self.max_zoom);
+            let anchor = area.screen_to_world(mouse);
+            area.zoom_around(anchor, wanted / zoom);
+        }
+    }
+}
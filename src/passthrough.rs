@@ -0,0 +1,84 @@
+//! Splitting pointer input between the area and UI around it, like egui panels.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+impl RenderArea2D {
+    /// Whether the pointer at `pos`, in window pixels, belongs to the area.
+    ///
+    /// This is true inside the presented area, and false over the letterbox bars and
+    /// [reserved margins](Self::reserve_margins), which are left to the UI.
+    /// With egui, give clicks to the game when this is true and to egui otherwise,
+    /// instead of guessing from egui's `wants_pointer_input` alone.
+    pub fn claims_pointer(&self, pos: impl Into<Vec2>) -> bool {
+        self.viewport_rect().contains(pos.into())
+    }
+    /// The parts of the window around the presented area, in window pixels.
+    ///
+    /// These are the letterbox bars and [reserved margins](Self::reserve_margins),
+    /// where UI can take input without fighting the area for it, see
+    /// [`Self::claims_pointer`]. Empty parts are left out.
+    pub fn ui_rects(&self) -> Vec<Rect> {
+        let outer = self
+            .viewport()
+            .unwrap_or_else(|| Rect::new(0.0, 0.0, screen_width(), screen_height()));
+        surrounding(outer, self.viewport_rect())
+    }
+}
+
+/// Up to four rects covering `outer` except for `inner`: top, bottom, left and right
+fn surrounding(outer: Rect, inner: Rect) -> Vec<Rect> {
+    let Some(inner) = outer.intersect(inner) else {
+        return vec![outer];
+    };
+    [
+        Rect::new(outer.x, outer.y, outer.w, inner.y - outer.y),
+        Rect::new(
+            outer.x,
+            inner.bottom(),
+            outer.w,
+            outer.bottom() - inner.bottom(),
+        ),
+        Rect::new(outer.x, inner.y, inner.x - outer.x, inner.h),
+        Rect::new(
+            inner.right(),
+            inner.y,
+            outer.right() - inner.right(),
+            inner.h,
+        ),
+    ]
+    .into_iter()
+    .filter(|r| r.w > 0.0 && r.h > 0.0)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTER: Rect = Rect::new(0.0, 0.0, 800.0, 600.0);
+
+    #[test]
+    fn letterbox_bars() {
+        assert_eq!(
+            surrounding(OUTER, Rect::new(100.0, 0.0, 600.0, 600.0)),
+            [
+                Rect::new(0.0, 0.0, 100.0, 600.0),
+                Rect::new(700.0, 0.0, 100.0, 600.0)
+            ]
+        );
+        assert!(surrounding(OUTER, OUTER).is_empty());
+        assert_eq!(
+            surrounding(OUTER, Rect::new(900.0, 0.0, 10.0, 10.0)),
+            [OUTER]
+        );
+    }
+
+    #[test]
+    fn margins_around() {
+        let rects = surrounding(OUTER, Rect::new(10.0, 20.0, 780.0, 540.0));
+        assert_eq!(rects.len(), 4);
+        let area: f32 = rects.iter().map(|r| r.w * r.h).sum();
+        assert_eq!(area, 800.0 * 600.0 - 780.0 * 540.0);
+    }
+}
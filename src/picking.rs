@@ -0,0 +1,32 @@
+//! World-space picking helpers.
+//!
+//! Everything is returned as plain glam values laid out the way rapier2d's query
+//! pipeline expects them: rays as `(origin, dir)` and AABBs as `(mins, maxs)`.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+impl RenderArea2D {
+    /// World-space point under the mouse cursor
+    pub fn pick_point(&self) -> Vec2 {
+        self.mouse_pos_cam()
+    }
+    /// Ray from `origin` towards the mouse cursor, as `(origin, dir)`.
+    ///
+    /// `dir` is not normalized, so a time of impact of `1.0` is exactly the cursor position.
+    pub fn pick_ray(&self, origin: impl Into<Vec2>) -> (Vec2, Vec2) {
+        let origin = origin.into();
+        (origin, self.pick_point() - origin)
+    }
+    /// World-space AABB around the mouse cursor, as `(mins, maxs)`
+    pub fn pick_aabb(&self, half_extents: impl Into<Vec2>) -> (Vec2, Vec2) {
+        let p = self.pick_point();
+        let he = half_extents.into();
+        (p - he, p + he)
+    }
+    /// World-space AABB of the visible area, as `(mins, maxs)`
+    pub fn visible_aabb(&self) -> (Vec2, Vec2) {
+        let r = self.visible_world_rect();
+        (r.point(), r.point() + r.size())
+    }
+}
@@ -0,0 +1,190 @@
+//! How the render area is placed in the window.
+
+use macroquad::prelude::*;
+
+/// How the render area is scaled to fit the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleMode {
+    /// The integer scale set with [`crate::RenderArea2D::set_scale`]
+    #[default]
+    Integer,
+    /// The biggest integer scale that fits, updated as the window resizes
+    IntegerAuto,
+    /// The biggest scale that fits, keeping the aspect ratio. Pixels may be uneven.
+    Fractional,
+    /// Fill the whole space, ignoring the aspect ratio
+    Stretch,
+    /// Fill the width, keeping the aspect ratio. The height may be cut off.
+    FitWidth,
+    /// Fill the height, keeping the aspect ratio. The width may be cut off.
+    FitHeight,
+    /// Fill the whole space, keeping the aspect ratio. One axis may be cut off.
+    Cover,
+    /// Grow the virtual resolution along one axis to fill the window instead of
+    /// letterboxing, showing more of the world.
+    ///
+    /// The size the area was created with is the minimum. The render target is
+    /// recreated by [`crate::RenderArea2D::update`] whenever the window aspect changes,
+    /// see [`crate::RenderArea2D::virtual_size`].
+    Expand,
+}
+
+/// Where a render area's [`ScaleMode`] comes from, see
+/// [`crate::RenderArea2D::set_scale_policy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalePolicy {
+    /// Pick a mode that suits the platform: [`ScaleMode::IntegerAuto`] on desktop,
+    /// [`ScaleMode::Fractional`] on the web, where the canvas size and DPI vary a lot,
+    /// and [`ScaleMode::Cover`] on mobile, so there are no bars on small screens
+    PlatformDefault,
+    /// Always use this mode
+    Fixed(ScaleMode),
+}
+
+impl ScalePolicy {
+    /// The scale mode this policy picks on the platform being compiled for
+    pub fn resolve(self) -> ScaleMode {
+        match self {
+            Self::Fixed(mode) => mode,
+            Self::PlatformDefault => {
+                if cfg!(target_arch = "wasm32") {
+                    ScaleMode::Fractional
+                } else if cfg!(any(target_os = "android", target_os = "ios")) {
+                    ScaleMode::Cover
+                } else {
+                    ScaleMode::IntegerAuto
+                }
+            }
+        }
+    }
+}
+
+/// How window positions are rounded to whole virtual pixels by the input mapping APIs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgePolicy {
+    /// Round down. The far edges of the area map to one past the last pixel.
+    #[default]
+    Exclusive,
+    /// Round down, but the far edges of the area still map to the last pixel
+    ClampInclusive,
+    /// Round to the nearest pixel, with halves rounding up.
+    /// The far edges of the area still map to the last pixel.
+    RoundHalfUp,
+}
+
+impl EdgePolicy {
+    /// Round `pos`, in virtual pixels, for an area `size` pixels big
+    pub fn apply(self, pos: Vec2, size: Vec2) -> Vec2 {
+        match self {
+            Self::Exclusive => pos.floor(),
+            Self::ClampInclusive => {
+                let edge = |v: f32, len: f32| if v == len { len - 1.0 } else { v.floor() };
+                vec2(edge(pos.x, size.x), edge(pos.y, size.y))
+            }
+            Self::RoundHalfUp => (pos + 0.5).floor().min(size - 1.0),
+        }
+    }
+}
+
+/// Where and how big the render area is presented in the window,
+/// see [`crate::RenderArea2D::presentation_transform`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PresentationTransform {
+    /// Window position of the top-left corner of the render area
+    pub offset: Vec2,
+    /// Window pixels per virtual pixel, per axis
+    pub scale: Vec2,
+}
+
+impl PresentationTransform {
+    /// Convert a point in virtual pixels to window coordinates
+    pub fn area_to_window(&self, point: Vec2) -> Vec2 {
+        self.offset + point * self.scale
+    }
+    /// Convert a point in window coordinates to virtual pixels
+    pub fn window_to_area(&self, point: Vec2) -> Vec2 {
+        (point - self.offset) / self.scale
+    }
+}
+
+/// A point on the edge or center of a rectangle, for placing things inside it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Anchor {
+    /// Top-left corner
+    TopLeft,
+    /// Middle of the top edge
+    Top,
+    /// Top-right corner
+    TopRight,
+    /// Middle of the left edge
+    Left,
+    /// Center
+    #[default]
+    Center,
+    /// Middle of the right edge
+    Right,
+    /// Bottom-left corner
+    BottomLeft,
+    /// Middle of the bottom edge
+    Bottom,
+    /// Bottom-right corner
+    BottomRight,
+}
+
+impl Anchor {
+    /// Top-left position for something `size` big, placed at this anchor of `outer`
+    pub fn place(self, outer: Rect, size: Vec2) -> Vec2 {
+        let (fx, fy) = match self {
+            Self::TopLeft => (0.0, 0.0),
+            Self::Top => (0.5, 0.0),
+            Self::TopRight => (1.0, 0.0),
+            Self::Left => (0.0, 0.5),
+            Self::Center => (0.5, 0.5),
+            Self::Right => (1.0, 0.5),
+            Self::BottomLeft => (0.0, 1.0),
+            Self::Bottom => (0.5, 1.0),
+            Self::BottomRight => (1.0, 1.0),
+        };
+        outer.point() + (outer.size() - size) * vec2(fx, fy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: Vec2 = vec2(320.0, 180.0);
+
+    #[test]
+    fn edge_policy_inside() {
+        let pos = vec2(10.4, 20.6);
+        assert_eq!(EdgePolicy::Exclusive.apply(pos, SIZE), vec2(10.0, 20.0));
+        assert_eq!(
+            EdgePolicy::ClampInclusive.apply(pos, SIZE),
+            vec2(10.0, 20.0)
+        );
+        assert_eq!(EdgePolicy::RoundHalfUp.apply(pos, SIZE), vec2(10.0, 21.0));
+        assert_eq!(
+            EdgePolicy::RoundHalfUp.apply(vec2(0.5, 1.5), SIZE),
+            vec2(1.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn edge_policy_far_edge() {
+        // The mouse at exactly `width * scale` used to map one past the last pixel
+        assert_eq!(EdgePolicy::Exclusive.apply(SIZE, SIZE), SIZE);
+        assert_eq!(
+            EdgePolicy::ClampInclusive.apply(SIZE, SIZE),
+            vec2(319.0, 179.0)
+        );
+        assert_eq!(
+            EdgePolicy::RoundHalfUp.apply(SIZE - 0.25, SIZE),
+            vec2(319.0, 179.0)
+        );
+        assert_eq!(
+            EdgePolicy::RoundHalfUp.apply(SIZE, SIZE),
+            vec2(319.0, 179.0)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Short screen-space reactions to gameplay events, like damage vignettes.
+
+use macroquad::prelude::*;
+
+/// Kind of pulse effect, see [`crate::RenderArea2D::pulse_effect`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EffectKind {
+    /// Red vignette, for taking damage
+    Damage,
+    /// Green vignette, for healing
+    Heal,
+    /// Vignette of an arbitrary color
+    Vignette(Color),
+    /// Flash of the whole area in an arbitrary color
+    Flash(Color),
+}
+
+impl EffectKind {
+    fn color(self) -> Color {
+        match self {
+            Self::Damage => Color::new(0.8, 0.0, 0.0, 1.0),
+            Self::Heal => Color::new(0.2, 1.0, 0.4, 1.0),
+            Self::Vignette(c) | Self::Flash(c) => c,
+        }
+    }
+}
+
+struct Pulse {
+    kind: EffectKind,
+    strength: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Pulse {
+    fn intensity(&self) -> f32 {
+        self.strength * (1.0 - self.elapsed / self.duration).max(0.0)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Pulses {
+    active: Vec<Pulse>,
+}
+
+impl Pulses {
+    pub(crate) fn push(&mut self, kind: EffectKind, strength: f32, duration: f32) {
+        if duration > 0.0 {
+            self.active.push(Pulse {
+                kind,
+                strength,
+                duration,
+                elapsed: 0.0,
+            });
+        }
+    }
+    pub(crate) fn update(&mut self, dt: f32) {
+        for p in &mut self.active {
+            p.elapsed += dt;
+        }
+        self.active.retain(|p| p.elapsed < p.duration);
+    }
+    /// Draw the active pulses over `rect` in window space
+    pub(crate) fn draw(&self, rect: Rect) {
+        // Pulses of the same kind stack up, but each kind saturates on its own
+        let mut totals: Vec<(EffectKind, f32)> = Vec::new();
+        for p in &self.active {
+            match totals.iter_mut().find(|(k, _)| *k == p.kind) {
+                Some((_, total)) => *total += p.intensity(),
+                None => totals.push((p.kind, p.intensity())),
+            }
+        }
+        for (kind, total) in totals {
+            let mut color = kind.color();
+            color.a *= total.min(1.0);
+            match kind {
+                EffectKind::Flash(_) => draw_rectangle(rect.x, rect.y, rect.w, rect.h, color),
+                _ => draw_vignette(rect, color),
+            }
+        }
+    }
+}
+
+/// Draw a frame around the inside of `rect`, fading from `color` at the edges to transparent
+fn draw_vignette(rect: Rect, color: Color) {
+    let inset = rect.w.min(rect.h) * 0.25;
+    let clear = Color { a: 0.0, ..color };
+    let (l, t, r, b) = (rect.x, rect.y, rect.x + rect.w, rect.y + rect.h);
+    let (il, it, ir, ib) = (l + inset, t + inset, r - inset, b - inset);
+    draw_mesh(&Mesh {
+        vertices: vec![
+            Vertex::new(l, t, 0.0, 0.0, 0.0, color),
+            Vertex::new(r, t, 0.0, 0.0, 0.0, color),
+            Vertex::new(r, b, 0.0, 0.0, 0.0, color),
+            Vertex::new(l, b, 0.0, 0.0, 0.0, color),
+            Vertex::new(il, it, 0.0, 0.0, 0.0, clear),
+            Vertex::new(ir, it, 0.0, 0.0, 0.0, clear),
+            Vertex::new(ir, ib, 0.0, 0.0, 0.0, clear),
+            Vertex::new(il, ib, 0.0, 0.0, 0.0, clear),
+        ],
+        indices: vec![
+            0, 1, 5, 0, 5, 4, // top
+            1, 2, 6, 1, 6, 5, // right
+            2, 3, 7, 2, 7, 6, // bottom
+            3, 0, 4, 3, 4, 7, // left
+        ],
+        texture: None,
+    });
+}
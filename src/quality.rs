@@ -0,0 +1,201 @@
+//! Presentation quality presets.
+
+use macroquad::prelude::*;
+
+/// Presentation settings bundled by a [`QualityPreset`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualitySettings {
+    /// Filter used when scaling the render area to the window
+    pub filter: FilterMode,
+    /// Whether post-processing passes run
+    pub effects: bool,
+    /// Whether temporal anti-aliasing is enabled
+    pub temporal_aa: bool,
+    /// Render at this multiple of the virtual resolution and scale down when presenting,
+    /// see [`crate::RenderArea2D::set_supersample`]
+    pub supersample: u8,
+    /// Frame rate to hold by lowering the supersampling while frames are slow, if any,
+    /// see [`crate::RenderArea2D::set_dynamic_resolution`]
+    pub dynamic_resolution: Option<f32>,
+}
+
+/// A bundle of presentation settings, see [`crate::RenderArea2D::apply_preset`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QualityPreset {
+    /// No post-processing, no anti-aliasing
+    Low,
+    /// Post-processing, no anti-aliasing
+    Medium,
+    /// Post-processing, temporal anti-aliasing, and 2x supersampling with smooth
+    /// downscaling, lowered as needed to hold 60 FPS
+    High,
+    /// Hand-picked settings
+    Custom(QualitySettings),
+}
+
+impl QualityPreset {
+    /// The settings this preset stands for
+    pub fn settings(self) -> QualitySettings {
+        let base = QualitySettings {
+            filter: FilterMode::Nearest,
+            effects: false,
+            temporal_aa: false,
+            supersample: 1,
+            dynamic_resolution: None,
+        };
+        match self {
+            Self::Low => base,
+            Self::Medium => QualitySettings {
+                effects: true,
+                ..base
+            },
+            Self::High => QualitySettings {
+                filter: FilterMode::Linear,
+                effects: true,
+                temporal_aa: true,
+                supersample: 2,
+                dynamic_resolution: Some(60.0),
+            },
+            Self::Custom(settings) => settings,
+        }
+    }
+}
+
+/// Seconds between supersampling changes of dynamic resolution
+const DYNAMIC_INTERVAL: f32 = 1.0;
+
+/// Lowers supersampling while frames take too long, and raises it back when they're fast
+pub(crate) struct DynamicResolution {
+    target_frame_time: f32,
+    /// Smoothed frame time
+    average: f32,
+    /// Time until the next change is allowed
+    cooldown: f32,
+}
+
+impl DynamicResolution {
+    pub(crate) fn new(target_fps: f32) -> Self {
+        let target_frame_time = 1.0 / target_fps;
+        Self {
+            target_frame_time,
+            average: target_frame_time,
+            cooldown: DYNAMIC_INTERVAL,
+        }
+    }
+    /// Record a frame. Returns the supersampling to switch to, if it should change.
+    pub(crate) fn record(&mut self, dt: f32, current: u8, max: u8) -> Option<u8> {
+        self.average += (dt - self.average) * 0.1;
+        self.cooldown -= dt;
+        if self.cooldown > 0.0 {
+            return None;
+        }
+        let next = if self.average > self.target_frame_time * 1.1 && current > 1 {
+            current - 1
+        } else if self.average < self.target_frame_time * 0.7 && current < max {
+            current + 1
+        } else {
+            return None;
+        };
+        self.cooldown = DYNAMIC_INTERVAL;
+        Some(next)
+    }
+}
+
+/// Presets tried during calibration, best first
+const CANDIDATES: [QualityPreset; 3] = [
+    QualityPreset::High,
+    QualityPreset::Medium,
+    QualityPreset::Low,
+];
+
+/// Frames ignored after switching presets, while things settle
+const WARMUP_FRAMES: u32 = 5;
+
+pub(crate) struct Calibration {
+    duration: f32,
+    target_frame_time: f32,
+    candidate: usize,
+    elapsed: f32,
+    frames: u32,
+    total_time: f32,
+}
+
+impl Calibration {
+    pub(crate) fn new(duration: f32, target_fps: f32) -> Self {
+        Self {
+            duration: duration / CANDIDATES.len() as f32,
+            target_frame_time: 1.0 / target_fps,
+            candidate: 0,
+            elapsed: 0.0,
+            frames: 0,
+            total_time: 0.0,
+        }
+    }
+    /// The preset currently being measured
+    pub(crate) fn current(&self) -> QualityPreset {
+        CANDIDATES[self.candidate]
+    }
+    /// Record a frame. Returns the chosen preset once calibration is done.
+    pub(crate) fn record(&mut self, dt: f32) -> Option<QualityPreset> {
+        self.frames += 1;
+        if self.frames > WARMUP_FRAMES {
+            self.elapsed += dt;
+            self.total_time += dt;
+        }
+        if self.frames <= WARMUP_FRAMES || self.elapsed < self.duration {
+            return None;
+        }
+        let measured = self.frames - WARMUP_FRAMES;
+        let average = self.total_time / measured as f32;
+        if average <= self.target_frame_time || self.candidate + 1 == CANDIDATES.len() {
+            return Some(self.current());
+        }
+        self.candidate += 1;
+        self.elapsed = 0.0;
+        self.frames = 0;
+        self.total_time = 0.0;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `dt` frames until calibration decides, or give up
+    fn run(calibration: &mut Calibration, dt: f32) -> Option<QualityPreset> {
+        (0..10_000).find_map(|_| calibration.record(dt))
+    }
+
+    #[test]
+    fn fast_keeps_high() {
+        let mut calibration = Calibration::new(3.0, 60.0);
+        assert_eq!(
+            run(&mut calibration, 1.0 / 120.0),
+            Some(QualityPreset::High)
+        );
+    }
+
+    #[test]
+    fn slow_steps_down() {
+        let mut calibration = Calibration::new(3.0, 60.0);
+        for _ in 0..WARMUP_FRAMES {
+            assert_eq!(calibration.record(1.0), None);
+        }
+        assert_eq!(calibration.current(), QualityPreset::High);
+        assert_eq!(run(&mut calibration, 1.0 / 20.0), Some(QualityPreset::Low));
+    }
+
+    #[test]
+    fn warmup_is_ignored() {
+        let mut calibration = Calibration::new(3.0, 60.0);
+        // Slow first frames, like shader compilation, don't count
+        for _ in 0..WARMUP_FRAMES {
+            assert_eq!(calibration.record(1.0), None);
+        }
+        assert_eq!(
+            run(&mut calibration, 1.0 / 120.0),
+            Some(QualityPreset::High)
+        );
+    }
+}
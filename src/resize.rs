@@ -0,0 +1,287 @@
+//! Reacting to window resizes, and changing the virtual resolution.
+
+use crate::quality::DynamicResolution;
+use crate::{RenderArea2D, RenderAreaEvent, ScaleMode};
+use macroquad::prelude::*;
+use std::cell::OnceCell;
+
+/// Called by [`RenderArea2D::handle_resize`] with the new window size
+pub(crate) type ResizeCallback = Box<dyn FnMut(&mut RenderArea2D, f32, f32)>;
+
+/// How much bigger than the virtual resolution the render target is allocated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetPadding {
+    /// Exactly the virtual resolution
+    #[default]
+    None,
+    /// Round each side up to a power of two, for GPUs that are slow with NPOT targets
+    PowerOfTwo,
+    /// Round each side up to a multiple of this many pixels
+    Align(u16),
+}
+
+impl TargetPadding {
+    /// The allocated size for a `w`x`h` target
+    pub fn apply(self, w: u16, h: u16) -> (u16, u16) {
+        match self {
+            Self::None => (w, h),
+            Self::PowerOfTwo => (w.next_power_of_two(), h.next_power_of_two()),
+            Self::Align(n) => {
+                let n = n.max(1);
+                (w.div_ceil(n) * n, h.div_ceil(n) * n)
+            }
+        }
+    }
+}
+
+impl RenderArea2D {
+    /// Check whether the window was resized or its DPI scale changed since the last call,
+    /// and react to it.
+    ///
+    /// On a change the scale is recomputed with [`Self::set_scale_auto`], which the
+    /// mouse mapping follows, then the [`Self::on_resize`] callback runs.
+    /// Call this once per frame, or enable the `web` feature to have [`Self::update`]
+    /// do it on the web, where the canvas can be resized by the page at any time.
+    /// Returns whether anything changed.
+    pub fn handle_resize(&mut self) -> bool {
+        let size = vec2(screen_width(), screen_height());
+        let current = (size, screen_dpi_scale());
+        if self.last_screen_size == Some(current) {
+            return false;
+        }
+        self.last_screen_size = Some(current);
+        self.set_scale_auto();
+        if let Some(mut callback) = self.on_resize.take() {
+            callback(self, size.x, size.y);
+            // The callback may have replaced itself
+            self.on_resize.get_or_insert(callback);
+        }
+        true
+    }
+    /// Run `callback` with the new window width and height whenever
+    /// [`Self::handle_resize`] notices a resize, e.g. to lay out the HUD again
+    pub fn on_resize(&mut self, callback: impl FnMut(&mut RenderArea2D, f32, f32) + 'static) {
+        self.on_resize = Some(Box::new(callback));
+    }
+    /// Make a new render target for the current size and settings.
+    ///
+    /// Everything sized like the render target is made again too.
+    pub(crate) fn recreate_render_target(&mut self) {
+        let (w, h) = self.alloc_size();
+        self.suspended = false;
+        self.render_target = render_target(w.into(), h.into());
+        self.render_target.texture.set_filter(self.filter);
+        self.camera.render_target = Some(self.render_target.clone());
+        self.camera.viewport = self.target_viewport();
+        if let Some(chain) = &mut self.effects {
+            chain.get_mut().resize(w, h);
+        }
+        if self.last_frame.is_some() {
+            self.last_frame = None;
+            self.set_keep_last_frame(true);
+        }
+        if self.mask_target.take().is_some() {
+            self.mask = None;
+        }
+        self.debug_step = None;
+        self.scroll_blit = None;
+        *self.composed.get_mut() = None;
+        self.report(RenderAreaEvent::TargetRecreated {
+            width: w,
+            height: h,
+        });
+    }
+    /// Make all GPU resources of the area again, e.g. after the GL context was lost.
+    ///
+    /// On WASM and mobile, losing the context leaves the render target invalid and the
+    /// area silently renders nothing. This recreates the render target and everything
+    /// sized like it, the presentation materials, and [cached layers](Self::cache_layer).
+    /// Settings like the scale mode, camera and [filter](Self::set_filter) are kept.
+    /// The contents are lost, so redraw everything afterwards.
+    /// A mask drawn with [`Self::begin_mask`] is dropped. Textures and materials you
+    /// passed in, like effect passes and the mask texture, are yours to recreate.
+    pub fn recreate_gpu_resources(&mut self) {
+        self.recreate_render_target();
+        self.blit_materials = OnceCell::new();
+        self.copy_material = OnceCell::new();
+        self.caches.clear();
+        self.chunked_caches.clear();
+    }
+    /// Free the render target and all other GPU resources of the area right away.
+    ///
+    /// Dropping the area does the same, this just makes it explicit at the call site.
+    /// Textures you got from the area, like [`Self::texture`], keep their own
+    /// texture alive until they are dropped too. Recreating the render target, like
+    /// [`Self::resize_virtual`] does, frees the old one the same way.
+    pub fn delete(self) {}
+    /// Release the GPU memory of the area while the app is in the background.
+    ///
+    /// Call this from the app's suspend event, like Android task switching, and
+    /// [`Self::on_resume`] when coming back. In between, [`Self::draw`] presents
+    /// nothing, drawing after [`Self::set`] is discarded, and captures are empty.
+    pub fn on_suspend(&mut self) {
+        self.suspended = true;
+        self.render_target = render_target(1, 1);
+        self.report(RenderAreaEvent::TargetRecreated {
+            width: 1,
+            height: 1,
+        });
+        self.camera.render_target = Some(self.render_target.clone());
+        self.camera.viewport = None;
+        if let Some(chain) = &mut self.effects {
+            chain.get_mut().resize(1, 1);
+        }
+        if self.mask_target.take().is_some() {
+            self.mask = None;
+        }
+        if self.last_frame.is_some() {
+            // A placeholder, so on_resume knows to make it again
+            self.last_frame = Some(render_target(1, 1));
+        }
+        self.debug_step = None;
+        self.scroll_blit = None;
+        self.transition = None;
+        *self.composed.get_mut() = None;
+        self.blit_materials = OnceCell::new();
+        self.caches.clear();
+        self.chunked_caches.clear();
+    }
+    /// Recreate the GPU resources after [`Self::on_suspend`] or a lost context, and
+    /// fit the area to the window again, which may have changed size or orientation.
+    ///
+    /// The contents are lost, so redraw everything afterwards.
+    pub fn on_resume(&mut self) {
+        self.recreate_gpu_resources();
+        self.set_scale_auto();
+    }
+    /// Call [`Self::recreate_gpu_resources`] from [`Self::update`] whenever the window
+    /// is restored, which is when mobile platforms may have dropped the GL context.
+    ///
+    /// macroquad can't tell whether the context was actually lost, so this recreates
+    /// on every restore.
+    pub fn set_recreate_on_restore(&mut self, recreate: bool) {
+        self.window_focus.subscribe();
+        self.recreate_on_restore = recreate;
+    }
+    /// Change the virtual resolution, recreating the render target.
+    ///
+    /// The contents are lost, so the area starts out blank. The camera keeps its target,
+    /// zoom and rotation, and effect passes and the [filter](Self::set_filter) are kept.
+    /// A mask started with [`Self::begin_mask`] is dropped.
+    ///
+    /// With [`ScaleMode::Expand`], this sets the minimum size the area expands from,
+    /// and with [`Self::set_supersample`] the size that gets multiplied.
+    pub fn resize_virtual(&mut self, width: u16, height: u16) {
+        if (width, height) == self.base_size {
+            return;
+        }
+        self.base_size = (width, height);
+        // Fits the actual size to the base size, scale mode and supersampling
+        self.set_scale_auto();
+    }
+    /// Change the size of the render area itself, without touching the base size
+    fn set_virtual_size(&mut self, width: u16, height: u16) {
+        if (width, height) == self.virtual_size() {
+            return;
+        }
+        let zoom = self.zoom();
+        (self.width, self.height) = (width, height);
+        self.set_zoom(zoom);
+        self.recreate_render_target();
+    }
+    /// Resize the area to what the [`ScaleMode`] wants for the current window:
+    /// the expanded size with [`ScaleMode::Expand`], the base size otherwise
+    pub(crate) fn fit_virtual_size(&mut self) {
+        let (w, h) = if self.scale_mode == ScaleMode::Expand {
+            self.expanded_size()
+        } else {
+            self.base_size
+        };
+        let factor = u16::from(self.supersample);
+        self.set_virtual_size(w.saturating_mul(factor), h.saturating_mul(factor));
+    }
+    /// Render at `factor` times the virtual resolution, and scale down when presenting.
+    ///
+    /// The view shows the same part of the world, with `factor` times as many pixels
+    /// each way. Virtual pixel coordinates, like [`Self::mouse_pos`] and
+    /// [`Self::virtual_size`], are in the bigger resolution. Use a linear
+    /// [filter](Self::set_filter) for smooth downscaling. 1 turns supersampling off.
+    pub fn set_supersample(&mut self, factor: u8) {
+        self.max_supersample = factor.max(1);
+        self.apply_supersample(self.max_supersample);
+    }
+    /// Change the supersampling in effect, keeping the view
+    pub(crate) fn apply_supersample(&mut self, factor: u8) {
+        if factor == self.supersample {
+            return;
+        }
+        let zoom = self.zoom() * f32::from(factor) / f32::from(self.supersample);
+        self.supersample = factor;
+        self.fit_virtual_size();
+        self.set_zoom(zoom);
+        self.set_scale_auto();
+    }
+    /// Lower the supersampling from [`Self::set_supersample`] while frames are slower
+    /// than `target_fps`, and raise it back once they're fast again, or `None` to
+    /// always use the full supersampling.
+    ///
+    /// Frame times are taken from [`Self::update`], and changes happen at most once
+    /// a second, since each one recreates the render target.
+    pub fn set_dynamic_resolution(&mut self, target_fps: Option<f32>) {
+        self.dynamic_resolution = target_fps.map(DynamicResolution::new);
+        if self.dynamic_resolution.is_none() {
+            self.apply_supersample(self.max_supersample);
+        }
+    }
+    /// The base size, grown along one axis to match the aspect ratio of the window
+    fn expanded_size(&self) -> (u16, u16) {
+        let avail = self.available_rect().size();
+        let base = vec2(f32::from(self.base_size.0), f32::from(self.base_size.1));
+        let scale = (avail / base).min_element();
+        if !scale.is_finite() || scale <= 0.0 {
+            return self.base_size;
+        }
+        let size = (avail / scale)
+            .round()
+            .max(base)
+            .min(Vec2::splat(f32::from(u16::MAX)));
+        (size.x as u16, size.y as u16)
+    }
+    /// The current virtual resolution.
+    ///
+    /// This is the size the area was created with, unless [`ScaleMode::Expand`]
+    /// grew it to fill the window.
+    pub fn virtual_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+    /// Allocate the render target padded with [`TargetPadding`], recreating it.
+    ///
+    /// Only the top-left virtual-resolution part of the target is drawn to and
+    /// presented, so this doesn't change what you see. Effect passes, the mask and
+    /// [`Self::texture`] get the padded textures, with the area in the top-left corner.
+    /// The contents of the area are lost when changing this.
+    pub fn set_target_padding(&mut self, padding: TargetPadding) {
+        if self.target_padding != padding {
+            self.target_padding = padding;
+            self.recreate_render_target();
+        }
+    }
+    /// The padding set with [`Self::set_target_padding`]
+    pub fn target_padding(&self) -> TargetPadding {
+        self.target_padding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_padding() {
+        assert_eq!(TargetPadding::None.apply(320, 180), (320, 180));
+        assert_eq!(TargetPadding::PowerOfTwo.apply(320, 180), (512, 256));
+        assert_eq!(TargetPadding::PowerOfTwo.apply(256, 1), (256, 1));
+        assert_eq!(TargetPadding::Align(16).apply(320, 180), (320, 192));
+        assert_eq!(TargetPadding::Align(0).apply(321, 180), (321, 180));
+    }
+}
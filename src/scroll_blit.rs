@@ -0,0 +1,153 @@
+//! Reusing the previous frame for scrolling content, redrawing only what scrolled in.
+
+use crate::{memory::texture_bytes, pixel_camera, RenderArea2D};
+use macroquad::prelude::*;
+
+pub(crate) struct ScrollBlit {
+    /// Copy of the previous frame, shifted back into the render target
+    scratch: RenderTarget,
+    /// Camera position in whole pixels, zoom and rotation at the last call
+    last: (IVec2, f32, f32),
+}
+
+impl ScrollBlit {
+    pub(crate) fn gpu_bytes(&self) -> usize {
+        texture_bytes(&self.scratch.texture)
+    }
+}
+
+impl RenderArea2D {
+    /// Draw scrolling content, reusing what's already in the render target.
+    ///
+    /// The previous contents are shifted by how far the camera moved in whole pixels,
+    /// and `redraw` is only called for the strips that scrolled into view, with their
+    /// world rect. It should draw everything overlapping that rect. The whole view is
+    /// redrawn the first time, whenever the zoom or rotation changed, and every frame
+    /// while the camera is rotated.
+    ///
+    /// Since everything drawn into the area is kept and shifted, this only makes
+    /// sense for an area holding nothing but the scrolling content, like a background
+    /// layer of a [`crate::LayerStack`], and with a camera that moves in whole pixels,
+    /// e.g. with [`Self::set_smooth_pixel_camera`]. For the same reason the
+    /// [clear color](Self::set_clear_color) isn't applied for the frame.
+    /// The camera is left set to the area afterwards.
+    pub fn scroll_redraw(&mut self, mut redraw: impl FnMut(Rect)) {
+        if self.suspended {
+            return;
+        }
+        // Clearing would throw away what gets shifted, so skip it for this frame
+        self.cleared_frame.set(Some(self.frame_count.get()));
+        let camera = self.camera_into(&self.render_target);
+        let zoom = self.zoom();
+        let pos = (camera.target * zoom).round().as_ivec2();
+        let (w, h) = self.target_size();
+        let size = ivec2(w.into(), h.into());
+        let now = (pos, zoom, camera.rotation);
+        // Strips can't be redrawn on their own when rotated, since they aren't axis-aligned
+        let delta = match &self.scroll_blit {
+            Some(sb)
+                if sb.last.1 == zoom && sb.last.2 == camera.rotation && camera.rotation == 0.0 =>
+            {
+                pos - sb.last.0
+            }
+            _ => size,
+        };
+        let state = self.scroll_blit.get_or_insert_with(|| {
+            let scratch = render_target(w.into(), h.into());
+            scratch.texture.set_filter(FilterMode::Nearest);
+            ScrollBlit { scratch, last: now }
+        });
+        state.last = now;
+        let strips = if delta.abs().cmpge(size).any() {
+            set_camera(&camera);
+            clear_background(BLANK);
+            vec![(IVec2::ZERO, size)]
+        } else if delta == IVec2::ZERO {
+            Vec::new()
+        } else {
+            set_camera(&pixel_camera(&state.scratch, w, h));
+            clear_background(BLANK);
+            draw_texture(&self.render_target.texture, 0.0, 0.0, WHITE);
+            set_camera(&pixel_camera(&self.render_target, w, h));
+            clear_background(BLANK);
+            draw_texture(
+                &state.scratch.texture,
+                -delta.x as f32,
+                -delta.y as f32,
+                WHITE,
+            );
+            exposed_strips(delta, size)
+        };
+        let to_world = camera.matrix().inverse();
+        let px_to_world = |px: IVec2| {
+            let clip = px.as_vec2() / size.as_vec2() * 2.0 - Vec2::ONE;
+            to_world.project_point3(clip.extend(0.0)).truncate()
+        };
+        for (min, strip) in strips {
+            // Bounding box of the corners, which also covers a rotated full view
+            let corners = [IVec2::ZERO, ivec2(strip.x, 0), ivec2(0, strip.y), strip]
+                .map(|corner| px_to_world(min + corner));
+            let lo = corners.into_iter().reduce(Vec2::min).unwrap_or_default();
+            let hi = corners.into_iter().reduce(Vec2::max).unwrap_or_default();
+            let world = Rect::new(lo.x, lo.y, hi.x - lo.x, hi.y - lo.y);
+            // The area's own camera, narrowed down to the strip, so flipping is kept
+            set_camera(&Camera2D {
+                target: world.center(),
+                zoom: camera.zoom * size.as_vec2() / strip.as_vec2(),
+                viewport: Some((min.x, min.y, strip.x, strip.y)),
+                render_target: camera.render_target.clone(),
+                ..camera
+            });
+            redraw(world);
+        }
+        set_camera(&camera);
+    }
+}
+
+/// Pixel rects (top-left, size) uncovered by shifting content by `-delta`
+fn exposed_strips(delta: IVec2, size: IVec2) -> Vec<(IVec2, IVec2)> {
+    let mut strips = Vec::new();
+    if delta.x > 0 {
+        strips.push((ivec2(size.x - delta.x, 0), ivec2(delta.x, size.y)));
+    } else if delta.x < 0 {
+        strips.push((IVec2::ZERO, ivec2(-delta.x, size.y)));
+    }
+    if delta.y > 0 {
+        strips.push((ivec2(0, size.y - delta.y), ivec2(size.x, delta.y)));
+    } else if delta.y < 0 {
+        strips.push((IVec2::ZERO, ivec2(size.x, -delta.y)));
+    }
+    strips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: IVec2 = ivec2(320, 180);
+
+    #[test]
+    fn no_motion() {
+        assert!(exposed_strips(IVec2::ZERO, SIZE).is_empty());
+    }
+
+    #[test]
+    fn one_axis() {
+        assert_eq!(
+            exposed_strips(ivec2(4, 0), SIZE),
+            [(ivec2(316, 0), ivec2(4, 180))]
+        );
+        assert_eq!(
+            exposed_strips(ivec2(0, -3), SIZE),
+            [(IVec2::ZERO, ivec2(320, 3))]
+        );
+    }
+
+    #[test]
+    fn both_axes() {
+        assert_eq!(
+            exposed_strips(ivec2(-2, 5), SIZE),
+            [(IVec2::ZERO, ivec2(2, 180)), (ivec2(0, 175), ivec2(320, 5))]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for the crate's built-in GLSL materials.
+
+use macroquad::miniquad::{BlendFactor, BlendState, BlendValue, Equation};
+use macroquad::prelude::*;
+
+/// Vertex shader shared by all built-in materials, same as macroquad's default
+pub(crate) const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec4 color;
+varying mediump vec2 uv;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}
+"#;
+
+/// Fragment shader copying the texture as-is, for use without blending
+pub(crate) const COPY: &str = r#"#version 100
+precision mediump float;
+varying vec2 uv;
+uniform sampler2D Texture;
+
+void main() {
+    gl_FragColor = texture2D(Texture, uv);
+}
+"#;
+
+/// Regular alpha blending, same as macroquad's default
+pub(crate) fn alpha_blend() -> Option<BlendState> {
+    Some(BlendState::new(
+        Equation::Add,
+        BlendFactor::Value(BlendValue::SourceAlpha),
+        BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+    ))
+}
+
+/// Blending for drawing premultiplied-alpha sources
+pub(crate) fn premultiplied_blend() -> Option<BlendState> {
+    Some(BlendState::new(
+        Equation::Add,
+        BlendFactor::One,
+        BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+    ))
+}
+
+/// Additive blending, scaled by source alpha
+pub(crate) fn additive_blend() -> Option<BlendState> {
+    Some(BlendState::new(
+        Equation::Add,
+        BlendFactor::Value(BlendValue::SourceAlpha),
+        BlendFactor::One,
+    ))
+}
+
+/// Additive blending for premultiplied-alpha sources
+pub(crate) fn premultiplied_additive_blend() -> Option<BlendState> {
+    Some(BlendState::new(
+        Equation::Add,
+        BlendFactor::One,
+        BlendFactor::One,
+    ))
+}
+
+/// Load a built-in material with the shared vertex shader.
+///
+/// The built-in shaders are simple enough to compile anywhere macroquad runs,
+/// so failure is treated as a bug.
+pub(crate) fn load(fragment: &str, params: MaterialParams) -> Material {
+    load_material(
+        ShaderSource::Glsl {
+            vertex: VERTEX,
+            fragment,
+        },
+        params,
+    )
+    .expect("built-in shader should compile")
+}
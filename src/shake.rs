@@ -0,0 +1,101 @@
+//! Trauma-based screen shake.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// How much [`RenderArea2D::shake`] moves versus rolls the camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShakeWeights {
+    /// Multiplier for the sideways and up-down offset
+    pub translation: f32,
+    /// Degrees of roll per world unit of shake intensity. Off by default, a little
+    /// roll makes heavy impacts feel less flat.
+    pub roll: f32,
+}
+
+impl Default for ShakeWeights {
+    fn default() -> Self {
+        Self {
+            translation: 1.0,
+            roll: 0.0,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Shake {
+    /// From 0 to 1. The shake amount is the square of this, so it falls off smoothly.
+    trauma: f32,
+    /// Biggest offset at full trauma, in world units
+    amplitude: f32,
+    /// Trauma lost per second
+    decay: f32,
+    /// Time the noise is sampled at
+    time: f32,
+    weights: ShakeWeights,
+}
+
+impl Shake {
+    pub(crate) fn update(&mut self, dt: f32) {
+        self.trauma = (self.trauma - self.decay * dt).max(0.0);
+        self.time += dt;
+    }
+    /// Offset to apply to the camera right now
+    pub(crate) fn offset(&self) -> Vec2 {
+        if self.trauma <= 0.0 {
+            return Vec2::ZERO;
+        }
+        vec2(self.noise(0.0), self.noise(100.0)) * self.strength() * self.weights.translation
+    }
+    /// Camera roll to apply right now, in degrees
+    pub(crate) fn roll(&self) -> f32 {
+        if self.trauma <= 0.0 || self.weights.roll == 0.0 {
+            return 0.0;
+        }
+        self.noise(200.0) * self.strength() * self.weights.roll
+    }
+    fn strength(&self) -> f32 {
+        self.amplitude * self.trauma * self.trauma
+    }
+    /// Sum of incommensurate sines, jittery enough to look random but still smooth
+    fn noise(&self, phase: f32) -> f32 {
+        let t = self.time + phase;
+        ((t * 41.0).sin() + (t * 67.3).sin() * 0.5 + (t * 13.7).sin() * 0.25) / 1.75
+    }
+}
+
+impl RenderArea2D {
+    /// Shake the view by up to `intensity` world units, dying down over `duration` seconds.
+    ///
+    /// Shaking is advanced by [`Self::update`], and only applied to the camera set by
+    /// [`Self::set`]. The real camera target is untouched, so mouse mapping and
+    /// following aren't thrown off. Overlapping shakes keep the stronger one going,
+    /// and a weaker shake can only make a stronger one last longer.
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        let shake = &mut self.shake;
+        let current = shake.strength();
+        let decay = if duration > 0.0 {
+            duration.recip()
+        } else {
+            f32::INFINITY
+        };
+        if current > intensity {
+            // Restart from full trauma at the current strength, with the longer time left
+            let remaining = shake.trauma / shake.decay;
+            shake.amplitude = current;
+            shake.decay = decay.min(remaining.recip());
+        } else {
+            shake.amplitude = intensity;
+            shake.decay = decay;
+        }
+        shake.trauma = 1.0;
+    }
+    /// Set how much shaking moves versus rolls the camera
+    pub fn set_shake_weights(&mut self, weights: ShakeWeights) {
+        self.shake.weights = weights;
+    }
+    /// Stop any shaking right away
+    pub fn stop_shake(&mut self) {
+        self.shake.trauma = 0.0;
+    }
+}
@@ -0,0 +1,37 @@
+//! Snapping world positions to the pixel grid, against shimmering sprites.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+impl RenderArea2D {
+    /// Round a world position to the nearest virtual pixel at the current zoom
+    pub fn snap(&self, world: impl Into<Vec2>) -> Vec2 {
+        let zoom = self.zoom();
+        (world.into() * zoom).round() / zoom
+    }
+    /// [Snap](Self::snap) the position and size of a world rect to whole virtual pixels
+    pub fn snap_rect(&self, rect: Rect) -> Rect {
+        let zoom = self.zoom();
+        let min = (rect.point() * zoom).round() / zoom;
+        let max = ((rect.point() + rect.size()) * zoom).round() / zoom;
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+    /// Snap the camera target to whole virtual pixels when drawing, so the pixel grid
+    /// of [snapped](Self::snap) sprites lines up with the render target.
+    ///
+    /// Only the camera used by [`Self::set`] is snapped. [`Self::camera_target`] and the
+    /// coordinate mapping keep the exact target, so game logic can move it smoothly.
+    pub fn set_snap_camera(&mut self, snap: bool) {
+        self.snap_camera = snap;
+    }
+    /// The camera target to draw with for `target`, snapped if enabled
+    pub(crate) fn snapped_target(&self, target: Vec2) -> Vec2 {
+        if !self.snap_camera {
+            return target;
+        }
+        // With an odd size, the view center is the middle of a pixel
+        let half = (self.size() / 2.0).fract();
+        let zoom = self.zoom();
+        ((target * zoom - half).round() + half) / zoom
+    }
+}
@@ -0,0 +1,55 @@
+//! Loading screens presented through the render area.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// What a splash screen shows, see [`RenderArea2D::draw_splash`]
+#[derive(Debug, Clone)]
+pub enum Splash {
+    /// Fill the area with a color
+    Color(Color),
+    /// A texture centered on black, at one texel per virtual pixel
+    Texture(Texture2D),
+}
+
+impl From<Color> for Splash {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<Texture2D> for Splash {
+    fn from(tex: Texture2D) -> Self {
+        Self::Texture(tex)
+    }
+}
+
+impl RenderArea2D {
+    /// Render a splash screen with an optional progress bar, and present it.
+    ///
+    /// `progress` goes from 0 to 1. This is meant for asset loading loops before the
+    /// main loop: call it, then `next_frame().await`. Everything is drawn in virtual
+    /// pixels, so it scales like the rest of the game. The camera is left at the default.
+    pub fn draw_splash(&self, splash: impl Into<Splash>, progress: Option<f32>) {
+        let size = self.size();
+        set_camera(&self.camera_for_pixels());
+        match splash.into() {
+            Splash::Color(color) => clear_background(color),
+            Splash::Texture(tex) => {
+                clear_background(BLACK);
+                let pos = ((size - tex.size()) / 2.0).floor();
+                draw_texture(&tex, pos.x, pos.y, WHITE);
+            }
+        }
+        if let Some(progress) = progress {
+            let bar = vec2((size.x * 0.6).floor(), (size.y / 30.0).floor().max(3.0));
+            let pos = vec2(((size.x - bar.x) / 2.0).floor(), (size.y * 0.8).floor());
+            draw_rectangle(pos.x, pos.y, bar.x, bar.y, DARKGRAY);
+            let filled = (bar.x * progress.clamp(0.0, 1.0)).floor();
+            draw_rectangle(pos.x, pos.y, filled, bar.y, WHITE);
+        }
+        set_default_camera();
+        clear_background(BLACK);
+        self.draw();
+    }
+}
@@ -0,0 +1,128 @@
+//! Several render areas sharing the window, for local multiplayer.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// How [`SplitScreen`] divides the window between its areas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitLayout {
+    /// Side by side, left to right
+    #[default]
+    Horizontal,
+    /// Stacked, top to bottom
+    Vertical,
+    /// A grid with as many columns as needed to be roughly square, filled row by row
+    Grid,
+}
+
+impl SplitLayout {
+    /// The rect of each of `n` parts of `outer`
+    pub fn rects(self, outer: Rect, n: usize) -> Vec<Rect> {
+        let (cols, rows) = match self {
+            Self::Horizontal => (n, 1),
+            Self::Vertical => (1, n),
+            Self::Grid => {
+                let cols = (n as f32).sqrt().ceil() as usize;
+                (cols, n.div_ceil(cols.max(1)))
+            }
+        };
+        let cell = vec2(outer.w / cols.max(1) as f32, outer.h / rows.max(1) as f32);
+        (0..n)
+            .map(|i| {
+                let (col, row) = (i % cols, i / cols);
+                Rect::new(
+                    outer.x + cell.x * col as f32,
+                    outer.y + cell.y * row as f32,
+                    cell.x,
+                    cell.y,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Areas with their own cameras, each presented in its part of the window.
+///
+/// Every area is a full [`RenderArea2D`], so draw the world into each one
+/// with its own camera, then call [`Self::draw`].
+pub struct SplitScreen {
+    /// The areas, in layout order
+    pub areas: Vec<RenderArea2D>,
+    layout: SplitLayout,
+}
+
+impl SplitScreen {
+    /// Split the window between `areas` with `layout`
+    pub fn new(areas: Vec<RenderArea2D>, layout: SplitLayout) -> Self {
+        let mut split = Self { areas, layout };
+        split.update_layout();
+        split
+    }
+    /// Change the layout
+    pub fn set_layout(&mut self, layout: SplitLayout) {
+        self.layout = layout;
+        self.update_layout();
+    }
+    /// Give every area its part of the window again.
+    ///
+    /// Call this after the window was resized or areas were added or removed.
+    pub fn update_layout(&mut self) {
+        let window = Rect::new(0.0, 0.0, screen_width(), screen_height());
+        let rects = self.layout.rects(window, self.areas.len());
+        for (area, rect) in self.areas.iter_mut().zip(rects) {
+            area.set_viewport(Some(rect));
+        }
+    }
+    /// Present all areas
+    pub fn draw(&self) {
+        for area in &self.areas {
+            area.draw();
+        }
+    }
+    /// The index of the area whose part of the window contains `window_pos`
+    pub fn area_for_point(&self, window_pos: impl Into<Vec2>) -> Option<usize> {
+        let pos = window_pos.into();
+        self.areas
+            .iter()
+            .position(|area| area.viewport().is_some_and(|rect| rect.contains(pos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTER: Rect = Rect::new(0.0, 0.0, 800.0, 600.0);
+
+    #[test]
+    fn split_rects() {
+        assert_eq!(
+            SplitLayout::Horizontal.rects(OUTER, 2),
+            [
+                Rect::new(0.0, 0.0, 400.0, 600.0),
+                Rect::new(400.0, 0.0, 400.0, 600.0)
+            ]
+        );
+        assert_eq!(
+            SplitLayout::Vertical.rects(OUTER, 2),
+            [
+                Rect::new(0.0, 0.0, 800.0, 300.0),
+                Rect::new(0.0, 300.0, 800.0, 300.0)
+            ]
+        );
+        assert!(SplitLayout::Grid.rects(OUTER, 0).is_empty());
+    }
+
+    #[test]
+    fn split_grid() {
+        let rects = SplitLayout::Grid.rects(OUTER, 3);
+        assert_eq!(
+            rects,
+            [
+                Rect::new(0.0, 0.0, 400.0, 300.0),
+                Rect::new(400.0, 0.0, 400.0, 300.0),
+                Rect::new(0.0, 300.0, 400.0, 300.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Queued subtitles drawn at window resolution over the area.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+
+/// How [`RenderArea2D::show_subtitle`] draws subtitles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubtitleStyle {
+    /// Font size in window pixels
+    pub font_size: u16,
+    /// Text color
+    pub color: Color,
+    /// Box drawn behind the lines, if any
+    pub background: Option<Color>,
+    /// Part of the presented area to keep clear at each edge, from 0 to 0.5,
+    /// so text stays readable on TVs and phones with rounded corners
+    pub safe_margin: f32,
+}
+
+impl Default for SubtitleStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 24,
+            color: WHITE,
+            background: Some(Color::new(0.0, 0.0, 0.0, 0.6)),
+            safe_margin: 0.05,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Subtitles {
+    /// Text and duration of the current subtitle, then the waiting ones
+    queue: VecDeque<(String, f32)>,
+    /// Seconds the current subtitle has been shown
+    elapsed: f32,
+    pub(crate) style: SubtitleStyle,
+}
+
+impl Subtitles {
+    pub(crate) fn update(&mut self, dt: f32) {
+        self.elapsed += dt;
+        while let Some((_, duration)) = self.queue.front() {
+            if self.elapsed < *duration {
+                break;
+            }
+            self.elapsed -= duration;
+            self.queue.pop_front();
+        }
+        if self.queue.is_empty() {
+            self.elapsed = 0.0;
+        }
+    }
+    /// Draw the current subtitle at the bottom of `rect`, in window coordinates
+    pub(crate) fn draw(&self, rect: Rect) {
+        let Some((text, _)) = self.queue.front() else {
+            return;
+        };
+        let style = &self.style;
+        let margin = rect.size() * style.safe_margin.clamp(0.0, 0.5);
+        let safe = Rect::new(
+            rect.x + margin.x,
+            rect.y + margin.y,
+            rect.w - margin.x * 2.0,
+            rect.h - margin.y * 2.0,
+        );
+        let lines = wrap(text, style.font_size, safe.w);
+        let line_height = f32::from(style.font_size);
+        let mut y = safe.bottom() - line_height * lines.len() as f32;
+        for line in &lines {
+            let dims = measure_text(line, None, style.font_size, 1.0);
+            let x = safe.x + (safe.w - dims.width) / 2.0;
+            if let Some(bg) = style.background {
+                let pad = line_height / 4.0;
+                draw_rectangle(x - pad, y, dims.width + pad * 2.0, line_height, bg);
+            }
+            let baseline = y + (line_height - dims.height) / 2.0 + dims.offset_y;
+            draw_text(line, x, baseline, line_height, style.color);
+            y += line_height;
+        }
+    }
+}
+
+/// Break `text` into lines no wider than `max_width` where possible, at spaces
+fn wrap(text: &str, font_size: u16, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+            if !line.is_empty() && measure_text(&candidate, None, font_size, 1.0).width > max_width
+            {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+impl RenderArea2D {
+    /// Show `text` as a subtitle for `duration` seconds, after the ones already queued.
+    ///
+    /// Subtitles are drawn by [`Self::draw`] at window resolution, centered at the
+    /// bottom of the presented area inside the safe margin, and wrapped to its width.
+    /// Time is advanced by [`Self::update`].
+    pub fn show_subtitle(&mut self, text: impl Into<String>, duration: f32) {
+        self.subtitles.queue.push_back((text.into(), duration));
+    }
+    /// Remove the current subtitle and all queued ones
+    pub fn clear_subtitles(&mut self) {
+        self.subtitles.queue.clear();
+        self.subtitles.elapsed = 0.0;
+    }
+    /// Set how subtitles look
+    pub fn set_subtitle_style(&mut self, style: SubtitleStyle) {
+        self.subtitles.style = style;
+    }
+}
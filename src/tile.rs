@@ -0,0 +1,49 @@
+//! Tile grid coordinate conversions.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+/// A tile grid laid out in world space.
+///
+/// Implement this for your tilemap type to get tile-based conversions
+/// through [`RenderArea2D`].
+pub trait TileSpace {
+    /// Size of a single tile in world units
+    fn tile_size(&self) -> Vec2;
+    /// World position of the top-left corner of tile (0, 0)
+    fn origin(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+    /// The tile containing the world position `pos`
+    fn world_to_tile(&self, pos: Vec2) -> IVec2 {
+        ((pos - self.origin()) / self.tile_size())
+            .floor()
+            .as_ivec2()
+    }
+    /// The world-space rectangle covered by `tile`
+    fn tile_to_world_rect(&self, tile: IVec2) -> Rect {
+        let size = self.tile_size();
+        let pos = self.origin() + tile.as_vec2() * size;
+        Rect::new(pos.x, pos.y, size.x, size.y)
+    }
+}
+
+impl RenderArea2D {
+    /// The tile under the mouse cursor
+    pub fn mouse_tile(&self, space: &impl TileSpace) -> IVec2 {
+        space.world_to_tile(self.mouse_pos_cam())
+    }
+    /// The tiles that are at least partially visible, as `(min, max)`.
+    ///
+    /// `min` is inclusive, `max` is exclusive.
+    pub fn visible_tiles(&self, space: &impl TileSpace) -> (IVec2, IVec2) {
+        let r = self.visible_world_rect();
+        let min = ((r.point() - space.origin()) / space.tile_size()).floor();
+        let max = ((r.point() + r.size() - space.origin()) / space.tile_size()).ceil();
+        (min.as_ivec2(), max.as_ivec2())
+    }
+    /// The world-space rectangle covered by `tile`
+    pub fn tile_to_world_rect(&self, space: &impl TileSpace, tile: IVec2) -> Rect {
+        space.tile_to_world_rect(tile)
+    }
+}
@@ -0,0 +1,57 @@
+//! Saving numbered frames at a fixed interval, for development time-lapses.
+
+use crate::capture::save_png;
+use crate::{RenderArea2D, RenderAreaEvent};
+use std::path::PathBuf;
+
+pub(crate) struct Timelapse {
+    interval: f32,
+    dir: PathBuf,
+    elapsed: f32,
+    next_frame: u32,
+}
+
+impl RenderArea2D {
+    /// Save the render area as `frame_NNNNN.png` into `dir` every `interval` seconds.
+    ///
+    /// Time is advanced by [`Self::update`]. Frames are saved at virtual resolution.
+    /// Failures are reported to the [diagnostics sink](Self::set_diagnostics).
+    pub fn capture_every(&mut self, interval: f32, dir: impl Into<PathBuf>) {
+        let dir = dir.into();
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            self.report(RenderAreaEvent::CaptureFailed {
+                path: dir.clone(),
+                error: e.to_string(),
+            });
+        }
+        self.timelapse = Some(Timelapse {
+            interval,
+            dir,
+            elapsed: 0.0,
+            next_frame: 0,
+        });
+    }
+    /// Stop capturing started by [`Self::capture_every`]
+    pub fn stop_capture(&mut self) {
+        self.timelapse = None;
+    }
+    pub(crate) fn update_timelapse(&mut self, dt: f32) {
+        let Some(tl) = &mut self.timelapse else {
+            return;
+        };
+        tl.elapsed += dt;
+        if tl.elapsed < tl.interval {
+            return;
+        }
+        tl.elapsed -= tl.interval;
+        let path = tl.dir.join(format!("frame_{:05}.png", tl.next_frame));
+        tl.next_frame += 1;
+        let img = self.capture();
+        if let Err(e) = save_png(&img, &path) {
+            self.report(RenderAreaEvent::CaptureFailed {
+                path,
+                error: e.to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! CPU timing of labelled scopes within a frame.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+use std::cell::RefCell;
+
+#[derive(Default)]
+pub(crate) struct Timings {
+    /// Scopes recorded this frame
+    current: RefCell<Vec<(&'static str, f64)>>,
+    /// Scopes recorded during the last presented frame
+    last: RefCell<Vec<(&'static str, f64)>>,
+}
+
+impl Timings {
+    pub(crate) fn record(&self, label: &'static str, secs: f64) {
+        let mut current = self.current.borrow_mut();
+        match current.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, total)) => *total += secs,
+            None => current.push((label, secs)),
+        }
+    }
+    /// Finish the frame, making its timings available
+    pub(crate) fn end_frame(&self) {
+        let mut current = self.current.borrow_mut();
+        let mut last = self.last.borrow_mut();
+        std::mem::swap(&mut *current, &mut *last);
+        current.clear();
+    }
+}
+
+/// A timed scope started with [`RenderArea2D::begin_timed`]. Timing stops when dropped.
+pub struct TimedScope<'a> {
+    timings: &'a Timings,
+    label: &'static str,
+    start: f64,
+}
+
+impl Drop for TimedScope<'_> {
+    fn drop(&mut self) {
+        self.timings.record(self.label, get_time() - self.start);
+    }
+}
+
+impl RenderArea2D {
+    /// Start timing a scope under `label`, until the returned guard is dropped.
+    ///
+    /// Scopes with the same label are summed up over the frame. Once the frame is
+    /// presented with [`Self::draw`], the results are available from
+    /// [`Self::frame_timings`]. The post-processing chain is timed as `"post"`.
+    ///
+    /// This measures CPU time, so GPU work that's merely queued up isn't included.
+    pub fn begin_timed(&self, label: &'static str) -> TimedScope<'_> {
+        TimedScope {
+            timings: &self.timings,
+            label,
+            start: get_time(),
+        }
+    }
+    /// Seconds spent in each timed scope during the last presented frame,
+    /// in the order the scopes first finished
+    pub fn frame_timings(&self) -> Vec<(&'static str, f64)> {
+        self.timings.last.borrow().clone()
+    }
+}
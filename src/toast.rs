@@ -0,0 +1,130 @@
+//! Achievement-style popups in a corner of the area.
+
+use crate::{draw_pixel_text, measure_pixel_text, Anchor, RenderArea2D};
+use macroquad::prelude::*;
+
+/// Seconds toasts take to slide in, and to fade out
+const ANIM: f32 = 0.25;
+/// Virtual pixels between toasts, the edge of the area and their contents
+const GAP: f32 = 2.0;
+
+struct Toast {
+    text: String,
+    icon: Option<Texture2D>,
+    duration: f32,
+    age: f32,
+}
+
+pub(crate) struct Toasts {
+    items: Vec<Toast>,
+    pub(crate) anchor: Anchor,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            anchor: Anchor::TopRight,
+        }
+    }
+}
+
+impl Toasts {
+    pub(crate) fn update(&mut self, dt: f32) {
+        for toast in &mut self.items {
+            toast.age += dt;
+        }
+        self.items.retain(|t| t.age < t.duration);
+    }
+    /// Draw the toasts, laid out in virtual pixels with `to_window` mapping them into
+    /// the window and `scale` window pixels per virtual pixel
+    pub(crate) fn draw(&self, area: Rect, to_window: impl Fn(Vec2) -> Vec2, scale: f32) {
+        let slide_dir = match self.anchor {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => vec2(-1.0, 0.0),
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => vec2(1.0, 0.0),
+            Anchor::Top | Anchor::Center => vec2(0.0, -1.0),
+            Anchor::Bottom => vec2(0.0, 1.0),
+        };
+        // Stack away from the anchored edge
+        let stack_dir = if matches!(
+            self.anchor,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight
+        ) {
+            -1.0
+        } else {
+            1.0
+        };
+        let inner = Rect::new(
+            area.x + GAP,
+            area.y + GAP,
+            area.w - GAP * 2.0,
+            area.h - GAP * 2.0,
+        );
+        let mut stacked = 0.0;
+        for toast in &self.items {
+            let text_size = measure_pixel_text(&toast.text, 1.0);
+            let icon_size = toast.icon.as_ref().map_or(Vec2::ZERO, |t| t.size());
+            let icon_gap = if toast.icon.is_some() { GAP } else { 0.0 };
+            let content = vec2(
+                icon_size.x + icon_gap + text_size.x,
+                icon_size.y.max(text_size.y),
+            );
+            let size = content + Vec2::splat(GAP * 2.0);
+            let slide = 1.0 - (toast.age / ANIM).min(1.0);
+            let alpha = ((toast.duration - toast.age) / ANIM).clamp(0.0, 1.0);
+            let mut pos = self.anchor.place(inner, size);
+            pos.y += stacked * stack_dir;
+            pos += slide_dir * (size + Vec2::splat(GAP)) * slide * slide;
+            stacked += size.y + GAP;
+            let top_left = to_window(pos);
+            let win = size * scale;
+            draw_rectangle(
+                top_left.x,
+                top_left.y,
+                win.x,
+                win.y,
+                Color::new(0.0, 0.0, 0.0, 0.75 * alpha),
+            );
+            let mut x = pos.x + GAP;
+            if let Some(icon) = &toast.icon {
+                let at = to_window(vec2(x, pos.y + (size.y - icon_size.y) / 2.0));
+                let params = DrawTextureParams {
+                    dest_size: Some(icon_size * scale),
+                    ..Default::default()
+                };
+                draw_texture_ex(icon, at.x, at.y, Color::new(1.0, 1.0, 1.0, alpha), params);
+                x += icon_size.x + icon_gap;
+            }
+            let at = to_window(vec2(x, pos.y + (size.y - text_size.y) / 2.0));
+            draw_pixel_text(
+                &toast.text,
+                at.x,
+                at.y,
+                scale,
+                Color::new(1.0, 1.0, 1.0, alpha),
+            );
+        }
+    }
+}
+
+impl RenderArea2D {
+    /// Pop up a notification with `text` and an optional `icon` for `duration` seconds.
+    ///
+    /// Toasts are laid out in virtual pixels in a corner of the area, so they look the
+    /// same at any window size, and stack when several are shown at once. They slide
+    /// in and fade out as [`Self::update`] advances time, and are drawn by [`Self::draw`].
+    /// The text uses the built-in pixel font, and icons are drawn at one texel per
+    /// virtual pixel.
+    pub fn toast(&mut self, text: impl Into<String>, icon: Option<Texture2D>, duration: f32) {
+        self.toasts.items.push(Toast {
+            text: text.into(),
+            icon,
+            duration,
+            age: 0.0,
+        });
+    }
+    /// Set which corner or edge of the area toasts appear at. The default is the top right.
+    pub fn set_toast_anchor(&mut self, anchor: Anchor) {
+        self.toasts.anchor = anchor;
+    }
+}
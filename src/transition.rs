@@ -0,0 +1,142 @@
+//! Sliding from one camera preset to another, like room transitions.
+
+use crate::{composite, memory::texture_bytes, pixel_camera, CameraState, Easing, RenderArea2D};
+use macroquad::prelude::*;
+use std::cell::OnceCell;
+
+/// Which way the view slides in [`RenderArea2D::transition_to_preset`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wipe {
+    /// The old view leaves to the left, the new one comes in from the right
+    SlideLeft,
+    /// The old view leaves to the right, the new one comes in from the left
+    SlideRight,
+    /// The old view leaves upwards, the new one comes in from the bottom
+    SlideUp,
+    /// The old view leaves downwards, the new one comes in from the top
+    SlideDown,
+}
+
+/// A running [`RenderArea2D::transition_to_preset`]
+pub(crate) struct Transition {
+    /// The last frame of the old view, at the virtual resolution
+    old: RenderTarget,
+    wipe: Wipe,
+    duration: f32,
+    elapsed: f32,
+}
+
+/// Source and destination fraction ranges of one view along the slide axis
+type Ranges = [(f32, f32); 2];
+
+/// The part of `rect` from fraction `a` to `b` along the x axis, or y if `vertical`
+fn part(rect: Rect, a: f32, b: f32, vertical: bool) -> Rect {
+    if vertical {
+        Rect::new(rect.x, rect.y + rect.h * a, rect.w, rect.h * (b - a))
+    } else {
+        Rect::new(rect.x + rect.w * a, rect.y, rect.w * (b - a), rect.h)
+    }
+}
+
+impl Transition {
+    /// Source and destination fraction ranges of the new and old view
+    fn ranges(&self) -> (Ranges, Ranges, bool) {
+        let p = if self.duration > 0.0 {
+            Easing::EaseInOut.apply(self.elapsed / self.duration)
+        } else {
+            1.0
+        };
+        let vertical = matches!(self.wipe, Wipe::SlideUp | Wipe::SlideDown);
+        match self.wipe {
+            Wipe::SlideLeft | Wipe::SlideUp => (
+                [(0.0, p), (1.0 - p, 1.0)],
+                [(p, 1.0), (0.0, 1.0 - p)],
+                vertical,
+            ),
+            Wipe::SlideRight | Wipe::SlideDown => (
+                [(1.0 - p, 1.0), (0.0, p)],
+                [(0.0, 1.0 - p), (p, 1.0)],
+                vertical,
+            ),
+        }
+    }
+    /// Where the new view goes: the `src` part of its texture and the `dest` window rect
+    pub(crate) fn place_new(&self, src: Rect, dest: Rect) -> (Rect, Rect) {
+        let ([(sa, sb), (da, db)], _, vertical) = self.ranges();
+        (part(src, sa, sb, vertical), part(dest, da, db, vertical))
+    }
+    /// Draw what's left of the old view, with `dest` the whole presented rect,
+    /// presented the same way as the new one
+    pub(crate) fn draw_old(
+        &self,
+        dest: Rect,
+        opts: &composite::BlitOptions,
+        materials: &OnceCell<composite::BlitMaterials>,
+    ) {
+        let (_, [(sa, sb), (da, db)], vertical) = self.ranges();
+        let size = self.old.texture.size();
+        let full = Rect::new(0.0, 0.0, size.x, size.y);
+        let src = part(full, sa, sb, vertical);
+        let dest = part(dest, da, db, vertical);
+        let params = DrawTextureParams {
+            dest_size: Some(dest.size()),
+            source: Some(src),
+            ..Default::default()
+        };
+        let opts = composite::BlitOptions {
+            mask: opts.mask.map(|mask| composite::Mask { dest: full, ..mask }),
+            ..*opts
+        };
+        composite::blit(&self.old.texture, dest.point(), params, &opts, materials);
+    }
+    pub(crate) fn gpu_bytes(&self) -> usize {
+        texture_bytes(&self.old.texture)
+    }
+}
+
+impl RenderArea2D {
+    /// Store a camera pose under `name`, for [`Self::transition_to_preset`]
+    pub fn add_camera_preset(&mut self, name: impl Into<String>, state: CameraState) {
+        self.camera_presets.insert(name.into(), state);
+    }
+    /// Slide from the current view to the camera preset `name` over `duration` seconds,
+    /// like the room transitions of classic adventure games.
+    ///
+    /// The current contents of the area are kept as the old view, and the camera jumps
+    /// to the preset right away, so draw the new room as usual. [`Self::draw`] slides
+    /// the old view out and the new one in, as [`Self::update`] advances time.
+    /// Effect passes only apply to the new view.
+    /// Returns whether there was a preset called `name`.
+    pub fn transition_to_preset(&mut self, name: &str, wipe: Wipe, duration: f32) -> bool {
+        let Some(&state) = self.camera_presets.get(name) else {
+            return false;
+        };
+        let old = render_target(self.width.into(), self.height.into());
+        old.texture.set_filter(FilterMode::Nearest);
+        push_camera_state();
+        set_camera(&pixel_camera(&old, self.width, self.height));
+        clear_background(BLANK);
+        draw_texture(&self.render_target.texture, 0.0, 0.0, WHITE);
+        pop_camera_state();
+        self.transition = Some(Transition {
+            old,
+            wipe,
+            duration,
+            elapsed: 0.0,
+        });
+        self.restore_state(state);
+        true
+    }
+    /// Whether a [`Self::transition_to_preset`] is still sliding
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+    pub(crate) fn update_transition(&mut self, dt: f32) {
+        if let Some(t) = &mut self.transition {
+            t.elapsed += dt;
+            if t.elapsed >= t.duration {
+                self.transition = None;
+            }
+        }
+    }
+}
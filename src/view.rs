@@ -0,0 +1,65 @@
+//! Queries against the visible part of the world.
+
+use crate::RenderArea2D;
+use macroquad::prelude::*;
+
+impl RenderArea2D {
+    /// Clip the world-space segment from `a` to `b` to the visible area.
+    ///
+    /// Returns the visible part of the segment, or `None` if it's entirely off-screen.
+    pub fn clip_segment_to_view(
+        &self,
+        a: impl Into<Vec2>,
+        b: impl Into<Vec2>,
+    ) -> Option<(Vec2, Vec2)> {
+        let (a, b) = (a.into(), b.into());
+        let view = self.visible_world_rect();
+        let d = b - a;
+        // Liang-Barsky: clip the segment's parameter range against each edge
+        let (mut t0, mut t1) = (0.0f32, 1.0f32);
+        for (p, q) in [
+            (-d.x, a.x - view.left()),
+            (d.x, view.right() - a.x),
+            (-d.y, a.y - view.top()),
+            (d.y, view.bottom() - a.y),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let t = q / p;
+                if p < 0.0 {
+                    t0 = t0.max(t);
+                } else {
+                    t1 = t1.min(t);
+                }
+            }
+        }
+        (t0 <= t1).then(|| (a + d * t0, a + d * t1))
+    }
+    /// Visibility of many world-space points at once. `true` means visible.
+    ///
+    /// The view bounds are computed once, and the per-point test is branchless, so this
+    /// is much faster than testing points one by one.
+    pub fn cull_points(&self, points: &[Vec2]) -> Vec<bool> {
+        let view = self.visible_world_rect();
+        let (min, max) = (view.point(), view.point() + view.size());
+        points
+            .iter()
+            .map(|p| (p.x >= min.x) & (p.x <= max.x) & (p.y >= min.y) & (p.y <= max.y))
+            .collect()
+    }
+    /// Visibility of many world-space rectangles at once. `true` means at least
+    /// partially visible.
+    ///
+    /// See [`Self::cull_points`].
+    pub fn cull_rects(&self, rects: &[Rect]) -> Vec<bool> {
+        let view = self.visible_world_rect();
+        let (min, max) = (view.point(), view.point() + view.size());
+        rects
+            .iter()
+            .map(|r| (r.x + r.w >= min.x) & (r.x <= max.x) & (r.y + r.h >= min.y) & (r.y <= max.y))
+            .collect()
+    }
+}
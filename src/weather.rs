@@ -0,0 +1,131 @@
+//! Rain and snow particles simulated in virtual coordinates.
+
+use macroquad::prelude::*;
+
+/// Kind of weather to simulate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherKind {
+    /// Fast falling streaks
+    Rain,
+    /// Slow drifting flakes
+    Snow,
+}
+
+/// Weather overlay configuration, see [`crate::RenderArea2D::set_weather`]
+#[derive(Debug, Clone)]
+pub struct Weather {
+    /// What kind of weather to simulate
+    pub kind: WeatherKind,
+    /// Particles per 100 square virtual pixels
+    pub density: f32,
+    /// Horizontal speed added to every particle, in virtual pixels per second
+    pub wind: f32,
+    /// Particle color
+    pub color: Color,
+}
+
+impl Weather {
+    /// Rain with sensible defaults
+    pub fn rain() -> Self {
+        Self {
+            kind: WeatherKind::Rain,
+            density: 0.5,
+            wind: 30.0,
+            color: Color::new(0.6, 0.7, 1.0, 0.6),
+        }
+    }
+    /// Snow with sensible defaults
+    pub fn snow() -> Self {
+        Self {
+            kind: WeatherKind::Snow,
+            density: 0.3,
+            wind: 10.0,
+            color: WHITE,
+        }
+    }
+}
+
+/// How long a rain streak is, in seconds of the drop's motion
+const STREAK_TIME: f32 = 1.0 / 60.0;
+
+struct Particle {
+    pos: Vec2,
+    /// Per-particle speed multiplier, for some depth variation
+    speed: f32,
+}
+
+pub(crate) struct WeatherState {
+    pub(crate) config: Weather,
+    particles: Vec<Particle>,
+    last_cam: Option<Vec2>,
+    /// Camera velocity in virtual pixels per second, which streaks are drawn against
+    cam_velocity: Vec2,
+    time: f32,
+}
+
+impl WeatherState {
+    pub(crate) fn new(config: Weather) -> Self {
+        Self {
+            config,
+            particles: Vec::new(),
+            last_cam: None,
+            cam_velocity: Vec2::ZERO,
+            time: 0.0,
+        }
+    }
+    fn velocity(&self, p: &Particle) -> Vec2 {
+        match self.config.kind {
+            WeatherKind::Rain => vec2(self.config.wind, 300.0) * p.speed,
+            WeatherKind::Snow => {
+                let sway = (self.time * 2.0 + p.pos.y * 0.1).sin() * 8.0;
+                vec2(self.config.wind + sway, 25.0) * p.speed
+            }
+        }
+    }
+    /// Advance the particles. `cam` is the camera target in world units, and `zoom`
+    /// the virtual pixels per world unit it's drawn at.
+    pub(crate) fn update(&mut self, dt: f32, cam: Vec2, zoom: f32, size: Vec2) {
+        self.time += dt;
+        let wanted = (self.config.density * size.x * size.y / 100.0).max(0.0) as usize;
+        self.particles.truncate(wanted);
+        while self.particles.len() < wanted {
+            self.particles.push(Particle {
+                pos: vec2(rand::gen_range(0.0, size.x), rand::gen_range(0.0, size.y)),
+                speed: rand::gen_range(0.7, 1.3),
+            });
+        }
+        // Particles are in view space, so the camera panning drags them the other way,
+        // making them look like they're part of the world
+        let cam_delta = self.last_cam.map_or(Vec2::ZERO, |last| (cam - last) * zoom);
+        self.last_cam = Some(cam);
+        if dt > 0.0 {
+            self.cam_velocity = cam_delta / dt;
+        }
+        for i in 0..self.particles.len() {
+            let step = self.velocity(&self.particles[i]) * dt - cam_delta;
+            let p = &mut self.particles[i];
+            p.pos = (p.pos + step).rem_euclid(size);
+        }
+    }
+    /// Draw the particles in virtual pixel coordinates, over the presented frame
+    pub(crate) fn draw(&self) {
+        let color = self.config.color;
+        for p in &self.particles {
+            match self.config.kind {
+                WeatherKind::Rain => {
+                    let motion = (self.velocity(p) - self.cam_velocity) * STREAK_TIME;
+                    let streak = if motion.length_squared() < 16.0 {
+                        vec2(0.0, 4.0)
+                    } else {
+                        motion
+                    };
+                    let tail = p.pos - streak;
+                    draw_line(p.pos.x, p.pos.y, tail.x, tail.y, 1.0, color);
+                }
+                WeatherKind::Snow => {
+                    draw_rectangle(p.pos.x.floor(), p.pos.y.floor(), 1.0, 1.0, color);
+                }
+            }
+        }
+    }
+}
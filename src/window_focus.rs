@@ -0,0 +1,97 @@
+//! Reacting to the window losing focus.
+
+use crate::RenderArea2D;
+use macroquad::input::utils::{register_input_subscriber, repeat_all_miniquad_input};
+use macroquad::miniquad::EventHandler;
+use macroquad::prelude::*;
+
+/// Tracks window focus through macroquad's minimized/restored events,
+/// which are what most platforms send on focus changes
+pub(crate) struct WindowFocus {
+    /// Registered on first use, so areas not using this don't collect events
+    subscriber: Option<usize>,
+    focused: bool,
+    /// Whether a restored event arrived since [`Self::take_restored`]
+    restored: bool,
+    pub(crate) dim: Option<Color>,
+    pub(crate) pause_effects: bool,
+    /// When effect time was paused, if it is
+    pause_started: Option<f64>,
+    /// Total seconds effect time was paused for
+    paused_total: f64,
+}
+
+impl Default for WindowFocus {
+    fn default() -> Self {
+        Self {
+            subscriber: None,
+            focused: true,
+            restored: false,
+            dim: None,
+            pause_effects: false,
+            pause_started: None,
+            paused_total: 0.0,
+        }
+    }
+}
+
+impl WindowFocus {
+    pub(crate) fn subscribe(&mut self) {
+        self.subscriber
+            .get_or_insert_with(register_input_subscriber);
+    }
+    pub(crate) fn update(&mut self) {
+        let Some(subscriber) = self.subscriber else {
+            return;
+        };
+        repeat_all_miniquad_input(self, subscriber);
+        let now = get_time();
+        match (self.pause_effects && !self.focused, self.pause_started) {
+            (true, None) => self.pause_started = Some(now),
+            (false, Some(start)) => {
+                self.paused_total += now - start;
+                self.pause_started = None;
+            }
+            _ => {}
+        }
+    }
+    /// Time for effect passes, standing still while paused
+    pub(crate) fn effect_time(&self) -> f64 {
+        self.pause_started.unwrap_or_else(get_time) - self.paused_total
+    }
+    /// Whether the window was restored since the last call
+    pub(crate) fn take_restored(&mut self) -> bool {
+        std::mem::take(&mut self.restored)
+    }
+    /// The dimming color to draw over the window right now, if any
+    pub(crate) fn active_dim(&self) -> Option<Color> {
+        self.dim.filter(|_| !self.focused)
+    }
+}
+
+impl EventHandler for WindowFocus {
+    fn update(&mut self) {}
+    fn draw(&mut self) {}
+    fn window_minimized_event(&mut self) {
+        self.focused = false;
+    }
+    fn window_restored_event(&mut self) {
+        self.focused = true;
+        self.restored = true;
+    }
+}
+
+impl RenderArea2D {
+    /// Dim the whole window with `color` while it doesn't have focus, or `None` to not dim.
+    ///
+    /// Focus changes are picked up by [`Self::update`].
+    pub fn set_unfocused_dim(&mut self, color: Option<Color>) {
+        self.window_focus.subscribe();
+        self.window_focus.dim = color;
+    }
+    /// Freeze the time effect passes see while the window doesn't have focus
+    pub fn set_pause_effects_when_unfocused(&mut self, pause: bool) {
+        self.window_focus.subscribe();
+        self.window_focus.pause_effects = pause;
+    }
+}